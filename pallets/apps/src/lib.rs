@@ -2,7 +2,15 @@
 
 pub use pallet::*;
 
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
 pub mod types;
+pub mod traits;
+pub mod migrations;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -10,14 +18,24 @@ pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_support::pallet_prelude::BoundedVec;
 	use frame_support::{Parameter, Blake2_128Concat};
-	use frame_support::traits::Time;
+	use frame_support::traits::{Time, Currency, ReservableCurrency};
 	use frame_support::inherent::Vec;
 	use frame_system::pallet_prelude::*;
-	use sp_runtime::traits::{AtLeast32Bit, Scale, CheckedAdd, Hash};
+	use sp_runtime::traits::{AtLeast32Bit, Scale, CheckedAdd, Hash, Saturating};
+	use sp_runtime::ArithmeticError;
+	use review_primitives::validation::{validate_text, TextError};
 	pub use crate::types::*;
+	pub use crate::traits::*;
+
+	pub type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// Version 1 adds the `AppHashes` and `SymbolIndex` indexes and moves stars set by root to
+	/// `StarOverrides`.
+	pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
@@ -45,12 +63,30 @@ pub mod pallet {
 
 		type AppTime: Time<Moment = Self::Moment>;
 
+		/// Currency used to reserve registration deposits.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// Deposit reserved for every app, on top of the per-byte deposit.
+		#[pallet::constant]
+		type AppDepositBase: Get<BalanceOf<Self>>;
+
+		/// Deposit reserved per byte of app name and symbol.
+		#[pallet::constant]
+		type DepositPerByte: Get<BalanceOf<Self>>;
+
+		/// Origin allowed to manage platform-wide settings such as categories.
+		type ForceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Notified when an app is destroyed, e.g. to clean up its reviews.
+		type OnAppDestroyed: OnAppDestroyed<Self::Hash>;
+
 		type Star: Parameter
 			+ Default
 			+ MaxEncodedLen
 			+ MaybeSerializeDeserialize
 			+ Copy
 			+ From<u32>
+			+ Into<u32>
 			+ PartialOrd;
 
 		#[pallet::constant]
@@ -59,11 +95,35 @@ pub mod pallet {
 		#[pallet::constant]
 		type StarLimit: Get<u32>;
 
+		/// Maximum number of maintainers of an app, owner included.
+		#[pallet::constant]
+		type MaxMaintainers: Get<u32>;
+
+		/// Number of releases kept per app, older ones are dropped first.
+		#[pallet::constant]
+		type MaxReleases: Get<u32>;
+
+		#[pallet::constant]
+		type CategoryNameLimit: Get<u32>;
+
+		#[pallet::constant]
+		type TagLimit: Get<u32>;
+
+		/// Maximum number of tags attached to an app.
+		#[pallet::constant]
+		type MaxTags: Get<u32>;
+
 		#[pallet::constant]
 		type AppNameLimit: Get<u32>;
 
+		#[pallet::constant]
+		type AppNameMinLimit: Get<u32>;
+
 		#[pallet::constant]
 		type AppSymbolLimit: Get<u32>;
+
+		#[pallet::constant]
+		type AppSymbolMinLimit: Get<u32>;
 	}
 
 	#[pallet::storage]
@@ -78,16 +138,107 @@ pub mod pallet {
 	#[pallet::getter(fn apps)]
 	pub(super) type Apps<T: Config> = StorageMap<_, Blake2_128Concat, T::Hash, App<T>, OptionQuery, >;
 
+	/// Hash of each app keyed by its sequential id.
+	#[pallet::storage]
+	#[pallet::getter(fn app_hash_of)]
+	pub(super) type AppHashes<T: Config> = StorageMap<_, Twox64Concat, T::AppId, T::Hash, OptionQuery, >;
+
 	#[pallet::storage]
 	#[pallet::getter(fn app_owner)]
 	pub(super) type AppsOnwer<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, BoundedVec<T::Hash, T::AppOwnerLimit>, ValueQuery, >;
 
+	/// Maintainers of each app and their role.
+	#[pallet::storage]
+	#[pallet::getter(fn maintainer)]
+	pub(super) type Maintainers<T: Config> = StorageDoubleMap<_, Blake2_128Concat, T::Hash, Blake2_128Concat, T::AccountId, MaintainerRole, OptionQuery, >;
+
+	#[pallet::storage]
+	#[pallet::getter(fn maintainer_count)]
+	pub(super) type MaintainerCount<T: Config> = StorageMap<_, Blake2_128Concat, T::Hash, u32, ValueQuery, >;
+
+	/// Release history of each app, oldest first.
+	#[pallet::storage]
+	#[pallet::getter(fn releases)]
+	pub(super) type Releases<T: Config> = StorageMap<_, Blake2_128Concat, T::Hash, BoundedVec<Release<T>, T::MaxReleases>, ValueQuery, >;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_category_id)]
+	pub(super) type NextCategoryId<T> = StorageValue<_, CategoryId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn categories)]
+	pub(super) type Categories<T: Config> = StorageMap<_, Blake2_128Concat, CategoryId, BoundedVec<u8, T::CategoryNameLimit>, OptionQuery, >;
+
+	/// Primary category of each app.
+	#[pallet::storage]
+	#[pallet::getter(fn app_category)]
+	pub(super) type AppCategory<T: Config> = StorageMap<_, Blake2_128Concat, T::Hash, CategoryId, OptionQuery, >;
+
+	#[pallet::storage]
+	#[pallet::getter(fn app_tags)]
+	pub(super) type AppTags<T: Config> = StorageMap<_, Blake2_128Concat, T::Hash, BoundedVec<BoundedVec<u8, T::TagLimit>, T::MaxTags>, ValueQuery, >;
+
+	/// Reverse index of `AppCategory`, iterate a category prefix to list its apps.
+	#[pallet::storage]
+	pub(super) type AppsByCategory<T: Config> = StorageDoubleMap<_, Blake2_128Concat, CategoryId, Blake2_128Concat, T::Hash, (), OptionQuery, >;
+
+	/// Reverse index of `AppTags`, iterate a tag prefix to list its apps.
+	#[pallet::storage]
+	pub(super) type AppsByTag<T: Config> = StorageDoubleMap<_, Blake2_128Concat, BoundedVec<u8, T::TagLimit>, Blake2_128Concat, T::Hash, (), OptionQuery, >;
+
+	/// Lowercased symbol of each app, so a symbol can only be used once whatever its case.
+	#[pallet::storage]
+	#[pallet::getter(fn app_by_symbol)]
+	pub(super) type SymbolIndex<T: Config> = StorageMap<_, Blake2_128Concat, BoundedVec<u8, T::AppSymbolLimit>, T::Hash, OptionQuery, >;
+
+	/// Lowercased symbols that governance keeps out of reach of `create_app` and `update`.
+	#[pallet::storage]
+	#[pallet::getter(fn protected_symbol)]
+	pub(super) type ProtectedSymbols<T: Config> = StorageMap<_, Blake2_128Concat, BoundedVec<u8, T::AppSymbolLimit>, (), OptionQuery, >;
+
+	/// Deposit currently reserved from the owner of each app.
+	#[pallet::storage]
+	#[pallet::getter(fn app_deposit)]
+	pub(super) type AppDeposits<T: Config> = StorageMap<_, Blake2_128Concat, T::Hash, BalanceOf<T>, ValueQuery, >;
+
+	/// Account an app is being handed to, until it accepts or the transfer is cancelled.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_transfer)]
+	pub(super) type PendingTransfers<T: Config> = StorageMap<_, Blake2_128Concat, T::Hash, T::AccountId, OptionQuery, >;
+
+	/// Apps frozen by their owner, with the moment they were archived.
+	#[pallet::storage]
+	#[pallet::getter(fn archived_at)]
+	pub(super) type ArchivedApps<T: Config> = StorageMap<_, Blake2_128Concat, T::Hash, T::Moment, OptionQuery, >;
+
+	/// Star forced by root, taking precedence over the star derived from reviews.
+	#[pallet::storage]
+	#[pallet::getter(fn star_override)]
+	pub(super) type StarOverrides<T: Config> = StorageMap<_, Blake2_128Concat, T::Hash, T::Star, OptionQuery, >;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		AppCreated { app_hash: T::Hash, app_id: T::AppId, who: T::AccountId },
 		AppUpdated { app_hash: T::Hash},
-		AppStarUpdated {app_hash: T::Hash, star: T::Star},
+		/// The star derived from reviews changed. Not emitted while root overrides the star.
+		AppStarUpdated {app_hash: T::Hash, star: Option<T::Star>},
+		StarOverrideSet {app_hash: T::Hash, star: T::Star},
+		StarOverrideCleared {app_hash: T::Hash},
+		TransferProposed {app_hash: T::Hash, from: T::AccountId, to: T::AccountId},
+		TransferCancelled {app_hash: T::Hash},
+		AppTransferred {app_hash: T::Hash, from: T::AccountId, to: T::AccountId},
+		AppArchived {app_hash: T::Hash},
+		AppDestroyed {app_hash: T::Hash, who: T::AccountId},
+		AppForceRemoved {app_hash: T::Hash, slashed: BalanceOf<T>},
+		SymbolReserved {symbol: BoundedVec<u8, T::AppSymbolLimit>},
+		SymbolReleased {symbol: BoundedVec<u8, T::AppSymbolLimit>},
+		MaintainerSet {app_hash: T::Hash, who: T::AccountId, role: MaintainerRole},
+		MaintainerRemoved {app_hash: T::Hash, who: T::AccountId},
+		ReleasePublished {app_hash: T::Hash, version: SemVer},
+		CategoryCreated {category_id: CategoryId},
+		CategoryRemoved {category_id: CategoryId},
+		TaxonomyUpdated {app_hash: T::Hash, category: Option<CategoryId>},
 	}
 
 	#[pallet::error]
@@ -98,30 +249,71 @@ pub mod pallet {
 		AppNotFound,
 		NotAppOwner,
 		StarLimited,
+		TransferToSelf,
+		NoPendingTransfer,
+		NotTransferRecipient,
+		AppArchived,
+		InsufficientRole,
+		MaintainerLimited,
+		NotMaintainer,
+		CannotChangeOwnerRole,
+		VersionNotIncreasing,
+		ReleaseLimited,
+		CategoryNotFound,
+		CategoryInUse,
+		CategoryNameTooLong,
+		TagTooLong,
+		TooManyTags,
+		DuplicateTag,
+		InsufficientDeposit,
+		SymbolTaken,
+		SymbolProtected,
+		SymbolNotProtected,
+		EmptyField,
+		InvalidUtf8,
+		NameTooShort,
+		NameTooLong,
+		SymbolTooShort,
+		SymbolTooLong,
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
+		#[pallet::call_index(0)]
 		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
 		pub fn create_app(origin: OriginFor<T>, name: Vec<u8>, symbol: Vec<u8>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
 			let app_id = Self::next_app_id();
 
-			let bounded_name: BoundedVec<u8, T::AppNameLimit> = name.clone().try_into().expect("app name is too long");
-			let bounded_symbol: BoundedVec<u8, T::AppSymbolLimit> = symbol.clone().try_into().expect("app symbol is too long");
+			let bounded_name = Self::bounded_name(name)?;
+			let bounded_symbol = Self::bounded_symbol(symbol)?;
+
+			let normalized_symbol = Self::normalize_symbol(&bounded_symbol);
+			Self::ensure_symbol_available(&normalized_symbol, None)?;
+
+			let deposit = Self::deposit_for(bounded_name.len(), bounded_symbol.len());
 
 			let app = App::create(app_id, who.clone(), bounded_name, bounded_symbol);
 
 			let app_hash = T::Hashing::hash_of(&app);
 
+			T::Currency::reserve(&who, deposit).map_err(|_| <Error<T>>::InsufficientDeposit)?;
+			<AppDeposits<T>>::insert(app_hash, deposit);
+
 			// storage app
 			<Apps<T>>::insert(app_hash, app);
+			<AppHashes<T>>::insert(app_id, app_hash);
+			<SymbolIndex<T>>::insert(normalized_symbol, app_hash);
 
 			// storage app owner
 			<AppsOnwer<T>>::try_mutate(&who, |app_vec| {
 				app_vec.try_push(app_hash)
 			}).map_err(|_| <Error<T>>::AppOwnerLimited)?;
+
+			// owner is the first admin of the app
+			<Maintainers<T>>::insert(&app_hash, &who, MaintainerRole::Admin);
+			<MaintainerCount<T>>::insert(&app_hash, 1);
 			
 			// set next app id
 			let next_app_id = Self::next_app_id().checked_add(&1_u32.into()).ok_or(<Error<T>>::AppNumberLimited)?;
@@ -136,18 +328,47 @@ pub mod pallet {
 			Ok(())
 		}
 
+		#[pallet::call_index(1)]
 		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
-		pub fn update(origin: OriginFor<T>, app_hash: T::Hash, name: Vec<u8>, symbol: Vec<u8>) -> DispatchResult {
+		pub fn update(origin: OriginFor<T>, app: AppRefOf<T>, name: Vec<u8>, symbol: Vec<u8>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			let app = <Apps<T>>::get(app_hash.clone()).ok_or(<Error<T>>::AppNotFound)?;
+			let app_hash = Self::resolve_app(&app)?;
+
+			ensure!(<Apps<T>>::contains_key(&app_hash), <Error<T>>::AppNotFound);
 			
-			// ensure who is app owner
-			ensure!(who == app.owner(), <Error<T>>::NotAppOwner);
+			// ensure who is allowed to edit the app
+			ensure!(Self::role_of(&app_hash, &who).map_or(false, |role| role.can_edit()), <Error<T>>::InsufficientRole);
+			ensure!(!Self::is_archived(&app_hash), <Error<T>>::AppArchived);
 
 			// ensure name and symbol valid
-			let bounded_name: BoundedVec<u8, T::AppNameLimit> = name.clone().try_into().expect("app name is too long");
-			let bounded_symbol: BoundedVec<u8, T::AppSymbolLimit> = symbol.clone().try_into().expect("app symbol is too long");
+			let bounded_name = Self::bounded_name(name)?;
+			let bounded_symbol = Self::bounded_symbol(symbol)?;
+
+			let app = Self::apps(&app_hash).ok_or(<Error<T>>::AppNotFound)?;
+
+			// move the symbol index entry when the symbol changes. An app the v1 migration left
+			// out of the index, because an older app holds the same symbol, has to pick a free one.
+			let old_symbol = Self::normalize_symbol(&app.symbol());
+			let new_symbol = Self::normalize_symbol(&bounded_symbol);
+			if old_symbol != new_symbol || Self::app_by_symbol(&new_symbol) != Some(app_hash) {
+				Self::ensure_symbol_available(&new_symbol, Some(&app_hash))?;
+				if Self::app_by_symbol(&old_symbol) == Some(app_hash) {
+					<SymbolIndex<T>>::remove(&old_symbol);
+				}
+				<SymbolIndex<T>>::insert(&new_symbol, app_hash);
+			}
+
+			// the owner deposit follows the size of name and symbol
+			let owner = app.owner();
+			let old_deposit = Self::app_deposit(&app_hash);
+			let new_deposit = Self::deposit_for(bounded_name.len(), bounded_symbol.len());
+			if new_deposit > old_deposit {
+				T::Currency::reserve(&owner, new_deposit - old_deposit).map_err(|_| <Error<T>>::InsufficientDeposit)?;
+			} else {
+				T::Currency::unreserve(&owner, old_deposit - new_deposit);
+			}
+			<AppDeposits<T>>::insert(&app_hash, new_deposit);
 
 			// change app info
 			<Apps<T>>::try_mutate(&app_hash, |app_option| {
@@ -164,28 +385,565 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Offer an app to `new_owner`. The transfer only happens once `new_owner` accepts it.
+		#[pallet::call_index(2)]
 		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
-		pub fn update_star(origin: OriginFor<T>, app_hash: T::Hash, star: T::Star) -> DispatchResult {
-			let _root = ensure_root(origin)?;
+		pub fn propose_transfer(origin: OriginFor<T>, app: AppRefOf<T>, new_owner: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
-			let star_limit = T::StarLimit::get();
+			let app_hash = Self::resolve_app(&app)?;
 
-			// ensure star less than star limit
-			ensure!(star <= star_limit.into(), <Error<T>>::StarLimited);
+			let app = <Apps<T>>::get(&app_hash).ok_or(<Error<T>>::AppNotFound)?;
 
-			<Apps<T>>::try_mutate(&app_hash, |app_option| {
-				if let Some(app) = app_option {
-					app.set_star(star);
-					return Ok(());
+			// ensure who is app owner
+			ensure!(who == app.owner(), <Error<T>>::NotAppOwner);
+			ensure!(who != new_owner, <Error<T>>::TransferToSelf);
+			ensure!(!Self::is_archived(&app_hash), <Error<T>>::AppArchived);
+
+			<PendingTransfers<T>>::insert(&app_hash, new_owner.clone());
+
+			Self::deposit_event(Event::TransferProposed {app_hash, from: who, to: new_owner});
+
+			Ok(())
+		}
+
+		#[pallet::call_index(3)]
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(4).ref_time())]
+		pub fn accept_transfer(origin: OriginFor<T>, app: AppRefOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let app_hash = Self::resolve_app(&app)?;
+
+			let recipient = <PendingTransfers<T>>::get(&app_hash).ok_or(<Error<T>>::NoPendingTransfer)?;
+			ensure!(who == recipient, <Error<T>>::NotTransferRecipient);
+
+			let mut app = <Apps<T>>::get(&app_hash).ok_or(<Error<T>>::AppNotFound)?;
+			let from = app.owner();
+			ensure!(!Self::is_archived(&app_hash), <Error<T>>::AppArchived);
+
+			// deposit is always held by the current owner
+			let deposit = Self::app_deposit(&app_hash);
+			T::Currency::reserve(&who, deposit).map_err(|_| <Error<T>>::InsufficientDeposit)?;
+			T::Currency::unreserve(&from, deposit);
+
+			// move app hash between owners
+			<AppsOnwer<T>>::try_mutate(&who, |app_vec| {
+				app_vec.try_push(app_hash)
+			}).map_err(|_| <Error<T>>::AppOwnerLimited)?;
+			<AppsOnwer<T>>::mutate(&from, |app_vec| {
+				app_vec.retain(|hash| *hash != app_hash)
+			});
+
+			// new owner takes the place of the old one as admin
+			if !<Maintainers<T>>::contains_key(&app_hash, &who) {
+				<MaintainerCount<T>>::mutate(&app_hash, |count| *count = count.saturating_add(1));
+			}
+			<Maintainers<T>>::insert(&app_hash, &who, MaintainerRole::Admin);
+			if <Maintainers<T>>::take(&app_hash, &from).is_some() {
+				<MaintainerCount<T>>::mutate(&app_hash, |count| *count = count.saturating_sub(1));
+			}
+
+			app.set_owner(who.clone());
+			<Apps<T>>::insert(&app_hash, app);
+			<PendingTransfers<T>>::remove(&app_hash);
+
+			Self::deposit_event(Event::AppTransferred {app_hash, from, to: who});
+
+			Ok(())
+		}
+
+		/// Withdraw a pending transfer. Either the owner or the proposed recipient can cancel it.
+		#[pallet::call_index(4)]
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
+		pub fn cancel_transfer(origin: OriginFor<T>, app: AppRefOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let app_hash = Self::resolve_app(&app)?;
+
+			let recipient = <PendingTransfers<T>>::get(&app_hash).ok_or(<Error<T>>::NoPendingTransfer)?;
+			let app = <Apps<T>>::get(&app_hash).ok_or(<Error<T>>::AppNotFound)?;
+
+			ensure!(who == app.owner() || who == recipient, <Error<T>>::NotAppOwner);
+
+			<PendingTransfers<T>>::remove(&app_hash);
+
+			Self::deposit_event(Event::TransferCancelled {app_hash});
+
+			Ok(())
+		}
+
+		/// Give `maintainer` a role on an app, or change the role it already has. Admin only.
+		#[pallet::call_index(5)]
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(2).ref_time())]
+		pub fn add_maintainer(origin: OriginFor<T>, app: AppRefOf<T>, maintainer: T::AccountId, role: MaintainerRole) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let app_hash = Self::resolve_app(&app)?;
+
+			let app = <Apps<T>>::get(&app_hash).ok_or(<Error<T>>::AppNotFound)?;
+
+			ensure!(Self::role_of(&app_hash, &who).map_or(false, |role| role.can_manage()), <Error<T>>::InsufficientRole);
+			ensure!(maintainer != app.owner(), <Error<T>>::CannotChangeOwnerRole);
+			ensure!(!Self::is_archived(&app_hash), <Error<T>>::AppArchived);
+
+			if !<Maintainers<T>>::contains_key(&app_hash, &maintainer) {
+				let count = Self::maintainer_count(&app_hash).max(1).checked_add(1).ok_or(<Error<T>>::MaintainerLimited)?;
+				ensure!(count <= T::MaxMaintainers::get(), <Error<T>>::MaintainerLimited);
+				<MaintainerCount<T>>::insert(&app_hash, count);
+			}
+			<Maintainers<T>>::insert(&app_hash, &maintainer, role);
+
+			Self::deposit_event(Event::MaintainerSet {app_hash, who: maintainer, role});
+
+			Ok(())
+		}
+
+		/// Remove a maintainer from an app. Admins can remove anyone but the owner, and any
+		/// maintainer can remove itself.
+		#[pallet::call_index(6)]
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(2).ref_time())]
+		pub fn remove_maintainer(origin: OriginFor<T>, app: AppRefOf<T>, maintainer: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let app_hash = Self::resolve_app(&app)?;
+
+			let app = <Apps<T>>::get(&app_hash).ok_or(<Error<T>>::AppNotFound)?;
+
+			ensure!(
+				who == maintainer || Self::role_of(&app_hash, &who).map_or(false, |role| role.can_manage()),
+				<Error<T>>::InsufficientRole
+			);
+			ensure!(maintainer != app.owner(), <Error<T>>::CannotChangeOwnerRole);
+			ensure!(!Self::is_archived(&app_hash), <Error<T>>::AppArchived);
+
+			<Maintainers<T>>::take(&app_hash, &maintainer).ok_or(<Error<T>>::NotMaintainer)?;
+			<MaintainerCount<T>>::mutate(&app_hash, |count| *count = count.saturating_sub(1));
+
+			Self::deposit_event(Event::MaintainerRemoved {app_hash, who: maintainer});
+
+			Ok(())
+		}
+
+		/// Publish a new release of an app. Its version must be greater than the latest one.
+		#[pallet::call_index(7)]
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
+		pub fn publish_release(
+			origin: OriginFor<T>,
+			app: AppRefOf<T>,
+			version: SemVer,
+			artifact_hash: T::Hash,
+			changelog_hash: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let app_hash = Self::resolve_app(&app)?;
+
+			let app = <Apps<T>>::get(&app_hash).ok_or(<Error<T>>::AppNotFound)?;
+
+			// ensure who is app owner
+			ensure!(who == app.owner(), <Error<T>>::NotAppOwner);
+			ensure!(!Self::is_archived(&app_hash), <Error<T>>::AppArchived);
+
+			let release = Release::<T> {
+				version,
+				artifact_hash,
+				changelog_hash,
+				published_at: T::AppTime::now(),
+			};
+
+			<Releases<T>>::try_mutate(&app_hash, |releases| -> DispatchResult {
+				if let Some(latest) = releases.last() {
+					ensure!(version > latest.version, <Error<T>>::VersionNotIncreasing);
 				}
-				Err(())
-			}).map_err(|_| <Error<T>>::AppNotFound)?;
+				// drop the oldest release once the history is full
+				if releases.len() as u32 >= T::MaxReleases::get() && !releases.is_empty() {
+					releases.remove(0);
+				}
+				releases.try_push(release).map_err(|_| <Error<T>>::ReleaseLimited)?;
+				Ok(())
+			})?;
 
-			Self::deposit_event(Event::AppStarUpdated {app_hash, star});
+			Self::deposit_event(Event::ReleasePublished {app_hash, version});
 
 			Ok(())
 		}
+
+		#[pallet::call_index(8)]
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(2).ref_time())]
+		pub fn create_category(origin: OriginFor<T>, name: Vec<u8>) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+
+			let bounded_name: BoundedVec<u8, T::CategoryNameLimit> = validate_text(name, 1)
+				.map_err(|error| Self::text_error(error, <Error<T>>::EmptyField, <Error<T>>::CategoryNameTooLong))?;
+
+			let category_id = Self::next_category_id();
+			let next_category_id = category_id.checked_add(1).ok_or(ArithmeticError::Overflow)?;
+
+			<Categories<T>>::insert(category_id, bounded_name);
+			<NextCategoryId<T>>::put(next_category_id);
+
+			Self::deposit_event(Event::CategoryCreated {category_id});
+
+			Ok(())
+		}
+
+		/// Remove a category. It must not be the primary category of any app.
+		#[pallet::call_index(9)]
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
+		pub fn remove_category(origin: OriginFor<T>, category_id: CategoryId) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+
+			ensure!(<Categories<T>>::contains_key(category_id), <Error<T>>::CategoryNotFound);
+			ensure!(<AppsByCategory<T>>::iter_prefix(category_id).next().is_none(), <Error<T>>::CategoryInUse);
+
+			<Categories<T>>::remove(category_id);
+
+			Self::deposit_event(Event::CategoryRemoved {category_id});
+
+			Ok(())
+		}
+
+		/// Set the primary category and the tags of an app, replacing the previous ones.
+		#[pallet::call_index(10)]
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(4 + 2 * T::MaxTags::get() as u64).ref_time())]
+		pub fn set_taxonomy(origin: OriginFor<T>, app: AppRefOf<T>, category: Option<CategoryId>, tags: Vec<Vec<u8>>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let app_hash = Self::resolve_app(&app)?;
+
+			ensure!(<Apps<T>>::contains_key(&app_hash), <Error<T>>::AppNotFound);
+
+			// ensure who is allowed to edit the app
+			ensure!(Self::role_of(&app_hash, &who).map_or(false, |role| role.can_edit()), <Error<T>>::InsufficientRole);
+			ensure!(!Self::is_archived(&app_hash), <Error<T>>::AppArchived);
+
+			if let Some(category_id) = category {
+				ensure!(<Categories<T>>::contains_key(category_id), <Error<T>>::CategoryNotFound);
+			}
+
+			let mut bounded_tags: BoundedVec<BoundedVec<u8, T::TagLimit>, T::MaxTags> = Default::default();
+			for tag in tags {
+				// tags are matched case-insensitively
+				let bounded_tag: BoundedVec<u8, T::TagLimit> = validate_text(tag.to_ascii_lowercase(), 1)
+					.map_err(|error| Self::text_error(error, <Error<T>>::EmptyField, <Error<T>>::TagTooLong))?;
+				ensure!(!bounded_tags.contains(&bounded_tag), <Error<T>>::DuplicateTag);
+				bounded_tags.try_push(bounded_tag).map_err(|_| <Error<T>>::TooManyTags)?;
+			}
+
+			Self::clear_taxonomy(&app_hash);
+
+			if let Some(category_id) = category {
+				<AppCategory<T>>::insert(&app_hash, category_id);
+				<AppsByCategory<T>>::insert(category_id, &app_hash, ());
+			}
+			for tag in bounded_tags.iter() {
+				<AppsByTag<T>>::insert(tag, &app_hash, ());
+			}
+			<AppTags<T>>::insert(&app_hash, bounded_tags);
+
+			Self::deposit_event(Event::TaxonomyUpdated {app_hash, category});
+
+			Ok(())
+		}
+
+		/// Keep a symbol out of reach of apps, e.g. for a well-known brand.
+		#[pallet::call_index(11)]
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
+		pub fn reserve_symbol(origin: OriginFor<T>, symbol: Vec<u8>) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+
+			let bounded_symbol = Self::bounded_symbol(symbol)?;
+			let symbol = Self::normalize_symbol(&bounded_symbol);
+
+			Self::ensure_symbol_available(&symbol, None)?;
+
+			<ProtectedSymbols<T>>::insert(&symbol, ());
+
+			Self::deposit_event(Event::SymbolReserved {symbol});
+
+			Ok(())
+		}
+
+		#[pallet::call_index(12)]
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
+		pub fn release_symbol(origin: OriginFor<T>, symbol: Vec<u8>) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+
+			let bounded_symbol = Self::bounded_symbol(symbol)?;
+			let symbol = Self::normalize_symbol(&bounded_symbol);
+
+			ensure!(<ProtectedSymbols<T>>::contains_key(&symbol), <Error<T>>::SymbolNotProtected);
+
+			<ProtectedSymbols<T>>::remove(&symbol);
+
+			Self::deposit_event(Event::SymbolReleased {symbol});
+
+			Ok(())
+		}
+
+		/// Freeze an app: it can no longer be updated, transferred or reviewed.
+		#[pallet::call_index(13)]
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
+		pub fn archive_app(origin: OriginFor<T>, app: AppRefOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let app_hash = Self::resolve_app(&app)?;
+
+			let app = <Apps<T>>::get(&app_hash).ok_or(<Error<T>>::AppNotFound)?;
+
+			// ensure who is app owner
+			ensure!(who == app.owner(), <Error<T>>::NotAppOwner);
+			ensure!(!Self::is_archived(&app_hash), <Error<T>>::AppArchived);
+
+			<ArchivedApps<T>>::insert(&app_hash, T::AppTime::now());
+			<PendingTransfers<T>>::remove(&app_hash);
+
+			Self::deposit_event(Event::AppArchived {app_hash});
+
+			Ok(())
+		}
+
+		/// Remove an app. Data other pallets keep for it is cleaned up through `OnAppDestroyed`.
+		#[pallet::call_index(14)]
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(6).ref_time())]
+		pub fn destroy_app(origin: OriginFor<T>, app: AppRefOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let app_hash = Self::resolve_app(&app)?;
+
+			let app = <Apps<T>>::get(&app_hash).ok_or(<Error<T>>::AppNotFound)?;
+
+			// ensure who is app owner
+			ensure!(who == app.owner(), <Error<T>>::NotAppOwner);
+
+			T::Currency::unreserve(&who, Self::app_deposit(&app_hash));
+			Self::remove_app(&app_hash, &app);
+
+			Self::deposit_event(Event::AppDestroyed {app_hash, who});
+
+			Ok(())
+		}
+
+		/// Remove an abusive app and slash the deposit of its owner.
+		#[pallet::call_index(15)]
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(7).ref_time())]
+		pub fn force_destroy_app(origin: OriginFor<T>, app: AppRefOf<T>) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+
+			let app_hash = Self::resolve_app(&app)?;
+
+			let app = <Apps<T>>::get(&app_hash).ok_or(<Error<T>>::AppNotFound)?;
+
+			let deposit = Self::app_deposit(&app_hash);
+			let (_, unslashed) = T::Currency::slash_reserved(&app.owner(), deposit);
+			Self::remove_app(&app_hash, &app);
+
+			Self::deposit_event(Event::AppForceRemoved {app_hash, slashed: deposit - unslashed});
+
+			Ok(())
+		}
+
+		/// Force the star shown for an app, or clear the override with `None` so the star
+		/// derived from reviews applies again.
+		#[pallet::call_index(16)]
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
+		pub fn set_star_override(origin: OriginFor<T>, app: AppRefOf<T>, star: Option<T::Star>) -> DispatchResult {
+			let _root = ensure_root(origin)?;
+
+			let app_hash = Self::resolve_app(&app)?;
+
+			ensure!(<Apps<T>>::contains_key(&app_hash), <Error<T>>::AppNotFound);
+
+			match star {
+				Some(star) => {
+					let star_limit = T::StarLimit::get();
+
+					// ensure star less than star limit
+					ensure!(star <= star_limit.into(), <Error<T>>::StarLimited);
+
+					<StarOverrides<T>>::insert(&app_hash, star);
+
+					Self::deposit_event(Event::StarOverrideSet {app_hash, star});
+				},
+				None => {
+					<StarOverrides<T>>::remove(&app_hash);
+
+					Self::deposit_event(Event::StarOverrideCleared {app_hash});
+				},
+			}
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Hash of the app `app` refers to. Only id references are checked against storage.
+		pub fn resolve_app(app: &AppRefOf<T>) -> Result<T::Hash, Error<T>> {
+			match app {
+				AppRef::Hash(app_hash) => Ok(*app_hash),
+				AppRef::Id(app_id) => Self::app_hash_of(app_id).ok_or(<Error<T>>::AppNotFound),
+			}
+		}
+
+		/// Role of `who` on an app. The owner is an admin even without a `Maintainers` entry.
+		pub fn role_of(app_hash: &T::Hash, who: &T::AccountId) -> Option<MaintainerRole> {
+			<Maintainers<T>>::get(app_hash, who).or_else(|| {
+				<Apps<T>>::get(app_hash)
+					.filter(|app| app.owner() == *who)
+					.map(|_| MaintainerRole::Admin)
+			})
+		}
+
+		pub fn is_maintainer(app_hash: &T::Hash, who: &T::AccountId) -> bool {
+			Self::role_of(app_hash, who).is_some()
+		}
+
+		fn bounded_name(name: Vec<u8>) -> Result<BoundedVec<u8, T::AppNameLimit>, Error<T>> {
+			validate_text(name, T::AppNameMinLimit::get())
+				.map_err(|error| Self::text_error(error, <Error<T>>::NameTooShort, <Error<T>>::NameTooLong))
+		}
+
+		fn bounded_symbol(symbol: Vec<u8>) -> Result<BoundedVec<u8, T::AppSymbolLimit>, Error<T>> {
+			validate_text(symbol, T::AppSymbolMinLimit::get())
+				.map_err(|error| Self::text_error(error, <Error<T>>::SymbolTooShort, <Error<T>>::SymbolTooLong))
+		}
+
+		/// Map a validation failure to the errors of the field being validated.
+		fn text_error(error: TextError, too_short: Error<T>, too_long: Error<T>) -> Error<T> {
+			match error {
+				TextError::Empty => <Error<T>>::EmptyField,
+				TextError::TooShort => too_short,
+				TextError::TooLong => too_long,
+				TextError::InvalidUtf8 => <Error<T>>::InvalidUtf8,
+			}
+		}
+
+		/// Symbols are compared case-insensitively.
+		pub fn normalize_symbol(symbol: &BoundedVec<u8, T::AppSymbolLimit>) -> BoundedVec<u8, T::AppSymbolLimit> {
+			let mut normalized = symbol.clone();
+			for byte in normalized.iter_mut() {
+				byte.make_ascii_lowercase();
+			}
+			normalized
+		}
+
+		/// Ensure a normalized symbol is neither protected nor used by an app other than `app_hash`.
+		fn ensure_symbol_available(symbol: &BoundedVec<u8, T::AppSymbolLimit>, app_hash: Option<&T::Hash>) -> DispatchResult {
+			ensure!(!<ProtectedSymbols<T>>::contains_key(symbol), <Error<T>>::SymbolProtected);
+			if let Some(owner_hash) = Self::app_by_symbol(symbol) {
+				ensure!(Some(&owner_hash) == app_hash, <Error<T>>::SymbolTaken);
+			}
+			Ok(())
+		}
+
+		/// Deposit for an app whose name and symbol have the given lengths.
+		fn deposit_for(name_len: usize, symbol_len: usize) -> BalanceOf<T> {
+			let bytes = (name_len + symbol_len) as u32;
+			T::AppDepositBase::get().saturating_add(T::DepositPerByte::get().saturating_mul(bytes.into()))
+		}
+
+		/// Drop the category and tags of an app along with their reverse index entries.
+		fn clear_taxonomy(app_hash: &T::Hash) {
+			if let Some(category_id) = <AppCategory<T>>::take(app_hash) {
+				<AppsByCategory<T>>::remove(category_id, app_hash);
+			}
+			for tag in <AppTags<T>>::take(app_hash).iter() {
+				<AppsByTag<T>>::remove(tag, app_hash);
+			}
+		}
+
+		/// Apps whose primary category is `category_id`.
+		pub fn apps_in_category(category_id: CategoryId) -> Vec<T::Hash> {
+			<AppsByCategory<T>>::iter_key_prefix(category_id).collect()
+		}
+
+		/// Apps tagged with `tag`, matched case-insensitively.
+		pub fn apps_with_tag(tag: &[u8]) -> Vec<T::Hash> {
+			match BoundedVec::<u8, T::TagLimit>::try_from(tag.to_ascii_lowercase()) {
+				Ok(tag) => <AppsByTag<T>>::iter_key_prefix(tag).collect(),
+				Err(_) => Vec::new(),
+			}
+		}
+
+		/// Release of an app with the given version, if it is still in the history.
+		pub fn release_of(app_hash: &T::Hash, version: &SemVer) -> Option<Release<T>> {
+			<Releases<T>>::get(app_hash).into_iter().find(|release| release.version == *version)
+		}
+
+		pub fn is_archived(app_hash: &T::Hash) -> bool {
+			<ArchivedApps<T>>::contains_key(app_hash)
+		}
+
+		fn remove_app(app_hash: &T::Hash, app: &App<T>) {
+			<Apps<T>>::remove(app_hash);
+			<AppHashes<T>>::remove(app.id());
+			let symbol = Self::normalize_symbol(&app.symbol());
+			if Self::app_by_symbol(&symbol) == Some(*app_hash) {
+				<SymbolIndex<T>>::remove(&symbol);
+			}
+			<AppsOnwer<T>>::mutate(app.owner(), |app_vec| {
+				app_vec.retain(|hash| hash != app_hash)
+			});
+			<AppsCount<T>>::mutate(|count| *count = count.saturating_sub(1));
+			<ArchivedApps<T>>::remove(app_hash);
+			<PendingTransfers<T>>::remove(app_hash);
+			<StarOverrides<T>>::remove(app_hash);
+			let _ = <Maintainers<T>>::clear_prefix(app_hash, T::MaxMaintainers::get(), None);
+			<MaintainerCount<T>>::remove(app_hash);
+			<Releases<T>>::remove(app_hash);
+			<AppDeposits<T>>::remove(app_hash);
+			Self::clear_taxonomy(app_hash);
+
+			T::OnAppDestroyed::on_app_destroyed(app_hash);
+		}
+
+		/// Star to display for an app: the root override if any, otherwise the star derived
+		/// from reviews.
+		pub fn star_of(app_hash: &T::Hash) -> Option<T::Star> {
+			<StarOverrides<T>>::get(app_hash).or_else(|| <Apps<T>>::get(app_hash).and_then(|app| app.star()))
+		}
+
+		/// Apps owned by `owner`, keyed by hash.
+		pub fn apps_of(owner: &T::AccountId) -> Vec<(T::Hash, App<T>)> {
+			Self::app_owner(owner)
+				.into_iter()
+				.filter_map(|app_hash| <Apps<T>>::get(app_hash).map(|app| (app_hash, app)))
+				.collect()
+		}
+
+		/// Up to `limit` apps with an id of at least `cursor`, ordered by id, and the id to
+		/// resume from when more may follow. Ids of destroyed apps are skipped.
+		pub fn apps_page(cursor: T::AppId, limit: u32) -> (Vec<(T::Hash, App<T>)>, Option<T::AppId>) {
+			let end: u32 = Self::next_app_id().into();
+			let mut id: u32 = cursor.into();
+			let mut apps = Vec::new();
+			while id < end && (apps.len() as u32) < limit {
+				if let Some(app_hash) = Self::app_hash_of(T::AppId::from(id)) {
+					if let Some(app) = <Apps<T>>::get(app_hash) {
+						apps.push((app_hash, app));
+					}
+				}
+				id = id.saturating_add(1);
+			}
+			let next = if id < end { Some(T::AppId::from(id)) } else { None };
+			(apps, next)
+		}
 	}
 
-	
+	impl<T: Config> AppRatingHandler<T::Hash, T::Star> for Pallet<T> {
+		fn on_rating_changed(app_hash: &T::Hash, star: Option<T::Star>) {
+			let updated = <Apps<T>>::mutate(app_hash, |app_option| {
+				if let Some(app) = app_option {
+					app.set_star(star);
+					return true;
+				}
+				false
+			});
+
+			// the override is what the app shows, the derived star changes unseen underneath it
+			if updated && !<StarOverrides<T>>::contains_key(app_hash) {
+				Self::deposit_event(Event::AppStarUpdated {app_hash: *app_hash, star});
+			}
+		}
+	}
 }
@@ -0,0 +1,84 @@
+use super::*;
+use core::marker::PhantomData;
+use frame_support::pallet_prelude::*;
+use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade};
+#[cfg(feature = "try-runtime")]
+use codec::{Decode, Encode};
+#[cfg(feature = "try-runtime")]
+use frame_support::inherent::Vec;
+
+pub mod v1 {
+    use super::*;
+
+    /// Build the `AppId -> Hash` and symbol indexes for apps stored before they existed.
+    ///
+    /// Symbols used to be unchecked, so several apps may share one. The app with the lowest
+    /// id keeps it; the others stay out of the symbol index until they are updated to a free
+    /// symbol.
+    ///
+    /// Stars written by root before they were derived from reviews move to `StarOverrides`,
+    /// so the first review does not silently replace them.
+    pub struct MigrateToV1<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let onchain_version = Pallet::<T>::on_chain_storage_version();
+            if onchain_version >= 1 {
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut count = 0u64;
+            for (app_hash, mut app) in Apps::<T>::iter() {
+                AppHashes::<T>::insert(app.id(), app_hash);
+
+                if let Some(star) = app.star() {
+                    StarOverrides::<T>::insert(app_hash, star);
+                    app.set_star(None);
+                    Apps::<T>::insert(app_hash, &app);
+                }
+
+                let symbol = Pallet::<T>::normalize_symbol(&app.symbol());
+                let holds_symbol = match SymbolIndex::<T>::get(&symbol) {
+                    Some(other_hash) => Apps::<T>::get(other_hash).map_or(true, |other| app.id() < other.id()),
+                    None => true,
+                };
+                if holds_symbol {
+                    SymbolIndex::<T>::insert(&symbol, app_hash);
+                }
+                count += 1;
+            }
+            StorageVersion::new(1).put::<Pallet<T>>();
+
+            T::DbWeight::get().reads_writes(count * 3 + 1, count * 4 + 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            let app_count = Apps::<T>::iter().count() as u32;
+            let star_count = Apps::<T>::iter_values().filter(|app| app.star().is_some()).count() as u32;
+            Ok((app_count, star_count).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            let (app_count, star_count) =
+                <(u32, u32)>::decode(&mut &state[..]).map_err(|_| "failed to decode app count")?;
+
+            ensure!(Pallet::<T>::on_chain_storage_version() == 1, "storage version not updated");
+            ensure!(AppHashes::<T>::iter().count() as u32 == app_count, "app index is incomplete");
+            for (app_id, app_hash) in AppHashes::<T>::iter() {
+                let app = Apps::<T>::get(app_hash).ok_or("app index points to a missing app")?;
+                ensure!(app.id() == app_id, "app index points to the wrong app");
+            }
+            ensure!(StarOverrides::<T>::iter().count() as u32 == star_count, "stars were not moved to overrides");
+            for app in Apps::<T>::iter_values() {
+                let symbol = Pallet::<T>::normalize_symbol(&app.symbol());
+                let holder = SymbolIndex::<T>::get(&symbol).ok_or("symbol of an app is not indexed")?;
+                let holder_app = Apps::<T>::get(holder).ok_or("symbol index points to a missing app")?;
+                ensure!(holder_app.id() <= app.id(), "symbol is not held by the oldest app");
+            }
+
+            Ok(())
+        }
+    }
+}
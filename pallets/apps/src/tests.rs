@@ -0,0 +1,732 @@
+use crate::{mock::*, migrations::v1::MigrateToV1, App, AppRatingHandler, AppRef, Error, Event, MaintainerRole, SemVer};
+use frame_support::{
+	assert_noop, assert_ok,
+	pallet_prelude::{BoundedVec, StorageVersion},
+	traits::{GetStorageVersion, OnRuntimeUpgrade, ReservableCurrency},
+};
+use sp_core::H256;
+
+#[test]
+fn create_app_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AppsModule::create_app(RuntimeOrigin::signed(1), b"Wallet".to_vec(), b"WLT".to_vec()));
+		assert_eq!(AppsModule::apps_count(), 1);
+		assert_eq!(AppsModule::app_owner(1).len(), 1);
+		// base deposit plus one per byte of name and symbol
+		assert_eq!(Balances::reserved_balance(1), 10 + 6 + 3);
+	});
+}
+
+#[test]
+fn create_app_rejects_empty_name() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AppsModule::create_app(RuntimeOrigin::signed(1), Vec::new(), b"WLT".to_vec()),
+			Error::<Test>::EmptyField
+		);
+	});
+}
+
+#[test]
+fn create_app_rejects_short_name() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AppsModule::create_app(RuntimeOrigin::signed(1), b"Wa".to_vec(), b"WLT".to_vec()),
+			Error::<Test>::NameTooShort
+		);
+	});
+}
+
+#[test]
+fn create_app_rejects_long_name() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AppsModule::create_app(RuntimeOrigin::signed(1), vec![b'a'; 17], b"WLT".to_vec()),
+			Error::<Test>::NameTooLong
+		);
+	});
+}
+
+#[test]
+fn create_app_rejects_short_symbol() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AppsModule::create_app(RuntimeOrigin::signed(1), b"Wallet".to_vec(), b"W".to_vec()),
+			Error::<Test>::SymbolTooShort
+		);
+	});
+}
+
+#[test]
+fn create_app_rejects_long_symbol() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AppsModule::create_app(RuntimeOrigin::signed(1), b"Wallet".to_vec(), vec![b'W'; 9]),
+			Error::<Test>::SymbolTooLong
+		);
+	});
+}
+
+#[test]
+fn create_app_rejects_invalid_utf8() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AppsModule::create_app(RuntimeOrigin::signed(1), vec![0xff, 0xfe, 0xfd], b"WLT".to_vec()),
+			Error::<Test>::InvalidUtf8
+		);
+	});
+}
+
+#[test]
+fn update_validates_input() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AppsModule::create_app(RuntimeOrigin::signed(1), b"Wallet".to_vec(), b"WLT".to_vec()));
+		let app_hash = AppsModule::app_owner(1)[0];
+
+		assert_noop!(
+			AppsModule::update(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), b"Wallet".to_vec(), Vec::new()),
+			Error::<Test>::EmptyField
+		);
+		assert_noop!(
+			AppsModule::update(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), b"Wallet".to_vec(), vec![0xc3, 0x28]),
+			Error::<Test>::InvalidUtf8
+		);
+		assert_ok!(AppsModule::update(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), b"Wallet 2".to_vec(), b"WLT2".to_vec()));
+	});
+}
+
+#[test]
+fn symbols_are_unique_ignoring_case() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AppsModule::create_app(RuntimeOrigin::signed(1), b"Wallet".to_vec(), b"WLT".to_vec()));
+		assert_noop!(
+			AppsModule::create_app(RuntimeOrigin::signed(2), b"Other".to_vec(), b"wlt".to_vec()),
+			Error::<Test>::SymbolTaken
+		);
+	});
+}
+
+#[test]
+fn apps_can_be_referenced_by_id() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AppsModule::create_app(RuntimeOrigin::signed(1), b"Wallet".to_vec(), b"WLT".to_vec()));
+		let app_hash = AppsModule::app_owner(1)[0];
+		let app_id = AppsModule::apps(app_hash).unwrap().id();
+
+		assert_eq!(AppsModule::app_hash_of(app_id), Some(app_hash));
+		assert_ok!(AppsModule::update(RuntimeOrigin::signed(1), AppRef::Id(app_id), b"Wallet 2".to_vec(), b"WLT".to_vec()));
+		assert_eq!(AppsModule::apps(app_hash).unwrap().name().to_vec(), b"Wallet 2".to_vec());
+		assert_noop!(
+			AppsModule::archive_app(RuntimeOrigin::signed(1), AppRef::Id(app_id + 1)),
+			Error::<Test>::AppNotFound
+		);
+	});
+}
+
+#[test]
+fn apps_page_walks_apps_by_id() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AppsModule::create_app(RuntimeOrigin::signed(1), b"Wallet".to_vec(), b"WLT".to_vec()));
+		assert_ok!(AppsModule::create_app(RuntimeOrigin::signed(2), b"Chat".to_vec(), b"CHT".to_vec()));
+		assert_ok!(AppsModule::create_app(RuntimeOrigin::signed(3), b"Maps".to_vec(), b"MAP".to_vec()));
+
+		let (apps, next) = AppsModule::apps_page(0, 2);
+		assert_eq!(apps.iter().map(|(_, app)| app.id()).collect::<Vec<_>>(), vec![0, 1]);
+		assert_eq!(next, Some(2));
+
+		let (apps, next) = AppsModule::apps_page(2, 2);
+		assert_eq!(apps.iter().map(|(_, app)| app.owner()).collect::<Vec<_>>(), vec![3]);
+		assert_eq!(next, None);
+		assert_eq!(AppsModule::apps_of(&2).len(), 1);
+	});
+}
+
+#[test]
+fn categories_are_managed_by_root() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AppsModule::create_category(RuntimeOrigin::signed(1), b"Finance".to_vec()),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			AppsModule::create_category(RuntimeOrigin::root(), vec![b'a'; 17]),
+			Error::<Test>::CategoryNameTooLong
+		);
+
+		assert_ok!(AppsModule::create_category(RuntimeOrigin::root(), b"Finance".to_vec()));
+		assert_eq!(AppsModule::categories(0).unwrap().to_vec(), b"Finance".to_vec());
+		assert_eq!(AppsModule::next_category_id(), 1);
+
+		assert_ok!(AppsModule::remove_category(RuntimeOrigin::root(), 0));
+		assert_eq!(AppsModule::categories(0), None);
+		assert_noop!(AppsModule::remove_category(RuntimeOrigin::root(), 0), Error::<Test>::CategoryNotFound);
+	});
+}
+
+#[test]
+fn category_ids_do_not_overflow() {
+	new_test_ext().execute_with(|| {
+		crate::pallet::NextCategoryId::<Test>::put(u32::MAX);
+		assert_noop!(
+			AppsModule::create_category(RuntimeOrigin::root(), b"Finance".to_vec()),
+			sp_runtime::ArithmeticError::Overflow
+		);
+	});
+}
+
+#[test]
+fn category_in_use_can_not_be_removed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AppsModule::create_category(RuntimeOrigin::root(), b"Finance".to_vec()));
+		assert_ok!(AppsModule::create_app(RuntimeOrigin::signed(1), b"Wallet".to_vec(), b"WLT".to_vec()));
+		let app_hash = AppsModule::app_owner(1)[0];
+
+		assert_ok!(AppsModule::set_taxonomy(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), Some(0), Vec::new()));
+		assert_noop!(AppsModule::remove_category(RuntimeOrigin::root(), 0), Error::<Test>::CategoryInUse);
+
+		assert_ok!(AppsModule::set_taxonomy(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), None, Vec::new()));
+		assert_ok!(AppsModule::remove_category(RuntimeOrigin::root(), 0));
+	});
+}
+
+#[test]
+fn set_taxonomy_replaces_indexes() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AppsModule::create_category(RuntimeOrigin::root(), b"Finance".to_vec()));
+		assert_ok!(AppsModule::create_category(RuntimeOrigin::root(), b"Social".to_vec()));
+		assert_ok!(AppsModule::create_app(RuntimeOrigin::signed(1), b"Wallet".to_vec(), b"WLT".to_vec()));
+		let app_hash = AppsModule::app_owner(1)[0];
+
+		assert_noop!(
+			AppsModule::set_taxonomy(RuntimeOrigin::signed(2), AppRef::Hash(app_hash), Some(0), Vec::new()),
+			Error::<Test>::InsufficientRole
+		);
+		assert_noop!(
+			AppsModule::set_taxonomy(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), Some(7), Vec::new()),
+			Error::<Test>::CategoryNotFound
+		);
+		assert_noop!(
+			AppsModule::set_taxonomy(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), None, vec![b"defi".to_vec(), b"DeFi".to_vec()]),
+			Error::<Test>::DuplicateTag
+		);
+		assert_noop!(
+			AppsModule::set_taxonomy(
+				RuntimeOrigin::signed(1),
+				AppRef::Hash(app_hash),
+				None,
+				vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec(), b"d".to_vec()]
+			),
+			Error::<Test>::TooManyTags
+		);
+		assert_noop!(
+			AppsModule::set_taxonomy(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), None, vec![vec![b'a'; 9]]),
+			Error::<Test>::TagTooLong
+		);
+
+		assert_ok!(AppsModule::set_taxonomy(
+			RuntimeOrigin::signed(1),
+			AppRef::Hash(app_hash),
+			Some(0),
+			vec![b"DeFi".to_vec(), b"wallet".to_vec()]
+		));
+		assert_eq!(AppsModule::app_category(app_hash), Some(0));
+		assert_eq!(AppsModule::apps_in_category(0), vec![app_hash]);
+		assert_eq!(AppsModule::apps_with_tag(b"defi"), vec![app_hash]);
+
+		// the previous category and tags are dropped from the reverse indexes
+		assert_ok!(AppsModule::set_taxonomy(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), Some(1), vec![b"chat".to_vec()]));
+		assert!(AppsModule::apps_in_category(0).is_empty());
+		assert_eq!(AppsModule::apps_in_category(1), vec![app_hash]);
+		assert!(AppsModule::apps_with_tag(b"DEFI").is_empty());
+		assert!(AppsModule::apps_with_tag(b"wallet").is_empty());
+		assert_eq!(AppsModule::apps_with_tag(b"chat"), vec![app_hash]);
+	});
+}
+
+#[test]
+fn destroying_app_clears_taxonomy() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AppsModule::create_category(RuntimeOrigin::root(), b"Finance".to_vec()));
+		assert_ok!(AppsModule::create_app(RuntimeOrigin::signed(1), b"Wallet".to_vec(), b"WLT".to_vec()));
+		let app_hash = AppsModule::app_owner(1)[0];
+		assert_ok!(AppsModule::set_taxonomy(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), Some(0), vec![b"defi".to_vec()]));
+
+		assert_ok!(AppsModule::destroy_app(RuntimeOrigin::signed(1), AppRef::Hash(app_hash)));
+		assert_eq!(AppsModule::app_category(app_hash), None);
+		assert!(AppsModule::app_tags(app_hash).is_empty());
+		assert!(AppsModule::apps_in_category(0).is_empty());
+		assert!(AppsModule::apps_with_tag(b"defi").is_empty());
+		assert_ok!(AppsModule::remove_category(RuntimeOrigin::root(), 0));
+	});
+}
+
+fn seed_old_app(app_hash: H256, id: u32, owner: u64, symbol: &[u8]) {
+	let app = App::<Test>::create(
+		id,
+		owner,
+		BoundedVec::truncate_from(b"Wallet".to_vec()),
+		BoundedVec::truncate_from(symbol.to_vec()),
+	);
+	crate::pallet::Apps::<Test>::insert(app_hash, app);
+}
+
+#[test]
+fn migration_builds_app_index() {
+	new_test_ext().execute_with(|| {
+		let first = H256::repeat_byte(1);
+		let second = H256::repeat_byte(2);
+		seed_old_app(first, 0, 1, b"WLT");
+		seed_old_app(second, 1, 2, b"CHT");
+		StorageVersion::new(0).put::<AppsModule>();
+
+		MigrateToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(AppsModule::app_hash_of(0), Some(first));
+		assert_eq!(AppsModule::app_hash_of(1), Some(second));
+		assert_eq!(AppsModule::on_chain_storage_version(), 1);
+	});
+}
+
+#[test]
+fn migration_indexes_symbols_keeping_the_oldest_app() {
+	new_test_ext().execute_with(|| {
+		let first = H256::repeat_byte(1);
+		let second = H256::repeat_byte(2);
+		seed_old_app(second, 1, 2, b"wlt");
+		seed_old_app(first, 0, 1, b"WLT");
+		StorageVersion::new(0).put::<AppsModule>();
+
+		MigrateToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(AppsModule::app_by_symbol(BoundedVec::truncate_from(b"wlt".to_vec())), Some(first));
+
+		// the app left out of the index has to move to a free symbol
+		assert_noop!(
+			AppsModule::update(RuntimeOrigin::signed(2), AppRef::Hash(second), b"Wallet".to_vec(), b"wlt".to_vec()),
+			Error::<Test>::SymbolTaken
+		);
+		assert_ok!(AppsModule::update(RuntimeOrigin::signed(2), AppRef::Hash(second), b"Wallet".to_vec(), b"WLT2".to_vec()));
+		assert_eq!(AppsModule::app_by_symbol(BoundedVec::truncate_from(b"wlt2".to_vec())), Some(second));
+		assert_eq!(AppsModule::app_by_symbol(BoundedVec::truncate_from(b"wlt".to_vec())), Some(first));
+	});
+}
+
+#[test]
+fn protected_symbols_can_not_be_used() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AppsModule::reserve_symbol(RuntimeOrigin::signed(1), b"DOT".to_vec()),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(AppsModule::reserve_symbol(RuntimeOrigin::root(), b"DOT".to_vec()));
+		assert!(AppsModule::protected_symbol(BoundedVec::truncate_from(b"dot".to_vec())).is_some());
+
+		assert_noop!(
+			AppsModule::create_app(RuntimeOrigin::signed(1), b"Polkadot".to_vec(), b"dot".to_vec()),
+			Error::<Test>::SymbolProtected
+		);
+		assert_noop!(
+			AppsModule::reserve_symbol(RuntimeOrigin::root(), b"Dot".to_vec()),
+			Error::<Test>::SymbolProtected
+		);
+	});
+}
+
+#[test]
+fn symbols_in_use_can_not_be_reserved() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AppsModule::create_app(RuntimeOrigin::signed(1), b"Wallet".to_vec(), b"WLT".to_vec()));
+		assert_noop!(
+			AppsModule::reserve_symbol(RuntimeOrigin::root(), b"wlt".to_vec()),
+			Error::<Test>::SymbolTaken
+		);
+	});
+}
+
+#[test]
+fn released_symbols_become_available() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AppsModule::release_symbol(RuntimeOrigin::root(), b"DOT".to_vec()),
+			Error::<Test>::SymbolNotProtected
+		);
+		assert_ok!(AppsModule::reserve_symbol(RuntimeOrigin::root(), b"DOT".to_vec()));
+		assert_ok!(AppsModule::release_symbol(RuntimeOrigin::root(), b"dot".to_vec()));
+		assert_eq!(AppsModule::protected_symbol(BoundedVec::truncate_from(b"dot".to_vec())), None);
+
+		assert_ok!(AppsModule::create_app(RuntimeOrigin::signed(1), b"Polkadot".to_vec(), b"DOT".to_vec()));
+	});
+}
+
+#[test]
+fn migration_moves_root_stars_to_overrides() {
+	new_test_ext().execute_with(|| {
+		let app_hash = H256::repeat_byte(1);
+		seed_old_app(app_hash, 0, 1, b"WLT");
+		crate::pallet::Apps::<Test>::mutate(app_hash, |app| app.as_mut().unwrap().set_star(Some(4)));
+		StorageVersion::new(0).put::<AppsModule>();
+
+		MigrateToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(AppsModule::star_override(app_hash), Some(4));
+		assert_eq!(AppsModule::apps(app_hash).unwrap().star(), None);
+
+		// the first derived rating does not replace the star root set
+		AppsModule::on_rating_changed(&app_hash, Some(2));
+		assert_eq!(AppsModule::star_of(&app_hash), Some(4));
+	});
+}
+
+#[test]
+fn star_override_takes_precedence_over_derived_star() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AppsModule::create_app(RuntimeOrigin::signed(1), b"Wallet".to_vec(), b"WLT".to_vec()));
+		let app_hash = AppsModule::app_owner(1)[0];
+		assert_eq!(AppsModule::star_of(&app_hash), None);
+
+		AppsModule::on_rating_changed(&app_hash, Some(3));
+		System::assert_last_event(Event::<Test>::AppStarUpdated { app_hash, star: Some(3) }.into());
+		assert_eq!(AppsModule::star_of(&app_hash), Some(3));
+
+		assert_noop!(
+			AppsModule::set_star_override(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), Some(5)),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			AppsModule::set_star_override(RuntimeOrigin::root(), AppRef::Hash(app_hash), Some(6)),
+			Error::<Test>::StarLimited
+		);
+
+		assert_ok!(AppsModule::set_star_override(RuntimeOrigin::root(), AppRef::Hash(app_hash), Some(5)));
+		System::assert_last_event(Event::<Test>::StarOverrideSet { app_hash, star: 5 }.into());
+		assert_eq!(AppsModule::star_of(&app_hash), Some(5));
+
+		// derived ratings keep being recorded underneath the override, without announcing a
+		// star the app does not show
+		AppsModule::on_rating_changed(&app_hash, Some(1));
+		assert_eq!(AppsModule::star_of(&app_hash), Some(5));
+		let derived: RuntimeEvent = Event::<Test>::AppStarUpdated { app_hash, star: Some(1) }.into();
+		assert!(!System::events().iter().any(|record| record.event == derived));
+
+		assert_ok!(AppsModule::set_star_override(RuntimeOrigin::root(), AppRef::Hash(app_hash), None));
+		System::assert_last_event(Event::<Test>::StarOverrideCleared { app_hash }.into());
+		assert_eq!(AppsModule::star_of(&app_hash), Some(1));
+	});
+}
+
+fn create_wallet() -> H256 {
+	assert_ok!(AppsModule::create_app(RuntimeOrigin::signed(1), b"Wallet".to_vec(), b"WLT".to_vec()));
+	AppsModule::app_owner(1)[0]
+}
+
+#[test]
+fn transfer_moves_app_and_deposit() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_wallet();
+		let deposit = AppsModule::app_deposit(app_hash);
+
+		assert_noop!(
+			AppsModule::propose_transfer(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), 1),
+			Error::<Test>::TransferToSelf
+		);
+		assert_noop!(
+			AppsModule::propose_transfer(RuntimeOrigin::signed(2), AppRef::Hash(app_hash), 3),
+			Error::<Test>::NotAppOwner
+		);
+		assert_noop!(
+			AppsModule::accept_transfer(RuntimeOrigin::signed(2), AppRef::Hash(app_hash)),
+			Error::<Test>::NoPendingTransfer
+		);
+
+		assert_ok!(AppsModule::propose_transfer(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), 2));
+		assert_eq!(AppsModule::pending_transfer(app_hash), Some(2));
+		assert_noop!(
+			AppsModule::accept_transfer(RuntimeOrigin::signed(3), AppRef::Hash(app_hash)),
+			Error::<Test>::NotTransferRecipient
+		);
+
+		assert_ok!(AppsModule::accept_transfer(RuntimeOrigin::signed(2), AppRef::Hash(app_hash)));
+		assert_eq!(AppsModule::apps(app_hash).unwrap().owner(), 2);
+		assert_eq!(AppsModule::pending_transfer(app_hash), None);
+		assert!(AppsModule::app_owner(1).is_empty());
+		assert_eq!(AppsModule::app_owner(2).to_vec(), vec![app_hash]);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), deposit);
+		assert_eq!(AppsModule::role_of(&app_hash, &2), Some(MaintainerRole::Admin));
+		assert_eq!(AppsModule::role_of(&app_hash, &1), None);
+	});
+}
+
+#[test]
+fn accept_transfer_needs_the_deposit() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_wallet();
+
+		// account 4 has no funds to hold the deposit
+		assert_ok!(AppsModule::propose_transfer(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), 4));
+		assert_noop!(
+			AppsModule::accept_transfer(RuntimeOrigin::signed(4), AppRef::Hash(app_hash)),
+			Error::<Test>::InsufficientDeposit
+		);
+	});
+}
+
+#[test]
+fn accept_transfer_respects_owner_limit() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_wallet();
+		for symbol in [b"AA", b"BB", b"CC", b"DD"] {
+			assert_ok!(AppsModule::create_app(RuntimeOrigin::signed(2), b"Other".to_vec(), symbol.to_vec()));
+		}
+
+		assert_ok!(AppsModule::propose_transfer(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), 2));
+		assert_noop!(
+			AppsModule::accept_transfer(RuntimeOrigin::signed(2), AppRef::Hash(app_hash)),
+			Error::<Test>::AppOwnerLimited
+		);
+	});
+}
+
+#[test]
+fn transfer_can_be_cancelled_by_either_side() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_wallet();
+
+		assert_ok!(AppsModule::propose_transfer(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), 2));
+		assert_noop!(
+			AppsModule::cancel_transfer(RuntimeOrigin::signed(3), AppRef::Hash(app_hash)),
+			Error::<Test>::NotAppOwner
+		);
+		assert_ok!(AppsModule::cancel_transfer(RuntimeOrigin::signed(2), AppRef::Hash(app_hash)));
+		assert_eq!(AppsModule::pending_transfer(app_hash), None);
+		assert_noop!(
+			AppsModule::accept_transfer(RuntimeOrigin::signed(2), AppRef::Hash(app_hash)),
+			Error::<Test>::NoPendingTransfer
+		);
+
+		assert_ok!(AppsModule::propose_transfer(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), 2));
+		assert_ok!(AppsModule::cancel_transfer(RuntimeOrigin::signed(1), AppRef::Hash(app_hash)));
+		assert_noop!(
+			AppsModule::cancel_transfer(RuntimeOrigin::signed(1), AppRef::Hash(app_hash)),
+			Error::<Test>::NoPendingTransfer
+		);
+	});
+}
+
+#[test]
+fn archived_app_is_frozen() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_wallet();
+		assert_ok!(AppsModule::propose_transfer(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), 2));
+
+		assert_noop!(
+			AppsModule::archive_app(RuntimeOrigin::signed(2), AppRef::Hash(app_hash)),
+			Error::<Test>::NotAppOwner
+		);
+		assert_ok!(AppsModule::archive_app(RuntimeOrigin::signed(1), AppRef::Hash(app_hash)));
+		assert!(AppsModule::is_archived(&app_hash));
+		// archiving drops a pending transfer
+		assert_eq!(AppsModule::pending_transfer(app_hash), None);
+
+		assert_noop!(
+			AppsModule::archive_app(RuntimeOrigin::signed(1), AppRef::Hash(app_hash)),
+			Error::<Test>::AppArchived
+		);
+		assert_noop!(
+			AppsModule::update(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), b"Wallet 2".to_vec(), b"WLT".to_vec()),
+			Error::<Test>::AppArchived
+		);
+		assert_noop!(
+			AppsModule::propose_transfer(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), 2),
+			Error::<Test>::AppArchived
+		);
+	});
+}
+
+#[test]
+fn destroy_app_removes_app_and_refunds() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_wallet();
+		let app_id = AppsModule::apps(app_hash).unwrap().id();
+		assert_ok!(AppsModule::add_maintainer(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), 2, MaintainerRole::Editor));
+		assert_ok!(AppsModule::archive_app(RuntimeOrigin::signed(1), AppRef::Hash(app_hash)));
+
+		assert_noop!(
+			AppsModule::destroy_app(RuntimeOrigin::signed(2), AppRef::Hash(app_hash)),
+			Error::<Test>::NotAppOwner
+		);
+		// archived apps can still be destroyed
+		assert_ok!(AppsModule::destroy_app(RuntimeOrigin::signed(1), AppRef::Hash(app_hash)));
+
+		assert!(AppsModule::apps(app_hash).is_none());
+		assert_eq!(AppsModule::app_hash_of(app_id), None);
+		assert_eq!(AppsModule::apps_count(), 0);
+		assert!(AppsModule::app_owner(1).is_empty());
+		assert!(!AppsModule::is_archived(&app_hash));
+		assert_eq!(AppsModule::maintainer(app_hash, 2), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+
+		// the symbol is free again
+		assert_ok!(AppsModule::create_app(RuntimeOrigin::signed(2), b"Wallet".to_vec(), b"WLT".to_vec()));
+	});
+}
+
+#[test]
+fn force_destroy_app_is_root_only() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_wallet();
+
+		assert_noop!(
+			AppsModule::force_destroy_app(RuntimeOrigin::signed(1), AppRef::Hash(app_hash)),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(AppsModule::force_destroy_app(RuntimeOrigin::root(), AppRef::Hash(app_hash)));
+		assert!(AppsModule::apps(app_hash).is_none());
+		assert_noop!(
+			AppsModule::force_destroy_app(RuntimeOrigin::root(), AppRef::Hash(app_hash)),
+			Error::<Test>::AppNotFound
+		);
+	});
+}
+
+#[test]
+fn admins_manage_maintainers() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_wallet();
+
+		assert_noop!(
+			AppsModule::add_maintainer(RuntimeOrigin::signed(2), AppRef::Hash(app_hash), 3, MaintainerRole::Editor),
+			Error::<Test>::InsufficientRole
+		);
+		assert_noop!(
+			AppsModule::add_maintainer(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), 1, MaintainerRole::Responder),
+			Error::<Test>::CannotChangeOwnerRole
+		);
+
+		assert_ok!(AppsModule::add_maintainer(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), 2, MaintainerRole::Editor));
+		assert_eq!(AppsModule::role_of(&app_hash, &2), Some(MaintainerRole::Editor));
+		// editors can edit the app but not manage maintainers
+		assert_ok!(AppsModule::update(RuntimeOrigin::signed(2), AppRef::Hash(app_hash), b"Wallet 2".to_vec(), b"WLT".to_vec()));
+		assert_noop!(
+			AppsModule::add_maintainer(RuntimeOrigin::signed(2), AppRef::Hash(app_hash), 3, MaintainerRole::Editor),
+			Error::<Test>::InsufficientRole
+		);
+
+		// changing a role does not count as a new maintainer
+		assert_ok!(AppsModule::add_maintainer(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), 2, MaintainerRole::Admin));
+		assert_eq!(AppsModule::maintainer_count(app_hash), 2);
+		assert_ok!(AppsModule::add_maintainer(RuntimeOrigin::signed(2), AppRef::Hash(app_hash), 3, MaintainerRole::Responder));
+		assert_noop!(
+			AppsModule::remove_maintainer(RuntimeOrigin::signed(2), AppRef::Hash(app_hash), 1),
+			Error::<Test>::CannotChangeOwnerRole
+		);
+	});
+}
+
+#[test]
+fn maintainers_are_bounded() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_wallet();
+
+		// the owner takes one of the `MaxMaintainers` seats
+		for maintainer in 2..5 {
+			assert_ok!(AppsModule::add_maintainer(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), maintainer, MaintainerRole::Responder));
+		}
+		assert_noop!(
+			AppsModule::add_maintainer(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), 5, MaintainerRole::Responder),
+			Error::<Test>::MaintainerLimited
+		);
+
+		assert_ok!(AppsModule::remove_maintainer(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), 4));
+		assert_ok!(AppsModule::add_maintainer(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), 5, MaintainerRole::Responder));
+	});
+}
+
+#[test]
+fn maintainers_can_leave() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_wallet();
+		assert_ok!(AppsModule::add_maintainer(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), 2, MaintainerRole::Responder));
+		assert_ok!(AppsModule::add_maintainer(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), 3, MaintainerRole::Responder));
+
+		assert_noop!(
+			AppsModule::remove_maintainer(RuntimeOrigin::signed(2), AppRef::Hash(app_hash), 3),
+			Error::<Test>::InsufficientRole
+		);
+		assert_ok!(AppsModule::remove_maintainer(RuntimeOrigin::signed(2), AppRef::Hash(app_hash), 2));
+		assert_eq!(AppsModule::role_of(&app_hash, &2), None);
+		assert_eq!(AppsModule::maintainer_count(app_hash), 2);
+		assert_noop!(
+			AppsModule::remove_maintainer(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), 2),
+			Error::<Test>::NotMaintainer
+		);
+	});
+}
+
+fn version(major: u16, minor: u16, patch: u16) -> SemVer {
+	SemVer { major, minor, patch }
+}
+
+#[test]
+fn releases_must_increase() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_wallet();
+		let artifact = H256::repeat_byte(1);
+		let changelog = H256::repeat_byte(2);
+
+		assert_noop!(
+			AppsModule::publish_release(RuntimeOrigin::signed(2), AppRef::Hash(app_hash), version(1, 0, 0), artifact, changelog),
+			Error::<Test>::NotAppOwner
+		);
+		assert_ok!(AppsModule::publish_release(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), version(1, 0, 0), artifact, changelog));
+		assert_noop!(
+			AppsModule::publish_release(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), version(1, 0, 0), artifact, changelog),
+			Error::<Test>::VersionNotIncreasing
+		);
+		assert_noop!(
+			AppsModule::publish_release(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), version(0, 9, 9), artifact, changelog),
+			Error::<Test>::VersionNotIncreasing
+		);
+		assert_ok!(AppsModule::publish_release(RuntimeOrigin::signed(1), AppRef::Hash(app_hash), version(1, 0, 1), artifact, changelog));
+
+		let release = AppsModule::release_of(&app_hash, &version(1, 0, 1)).unwrap();
+		assert_eq!(release.artifact_hash, artifact);
+		assert_eq!(release.changelog_hash, changelog);
+	});
+}
+
+#[test]
+fn oldest_release_is_dropped_when_history_is_full() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_wallet();
+		for minor in 0..4 {
+			assert_ok!(AppsModule::publish_release(
+				RuntimeOrigin::signed(1),
+				AppRef::Hash(app_hash),
+				version(1, minor, 0),
+				H256::repeat_byte(1),
+				H256::repeat_byte(2)
+			));
+		}
+
+		let versions: Vec<SemVer> = AppsModule::releases(app_hash).iter().map(|release| release.version).collect();
+		assert_eq!(versions, vec![version(1, 1, 0), version(1, 2, 0), version(1, 3, 0)]);
+		assert!(AppsModule::release_of(&app_hash, &version(1, 0, 0)).is_none());
+	});
+}
+
+#[test]
+fn force_destroy_app_slashes_deposit() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_wallet();
+		let deposit = AppsModule::app_deposit(app_hash);
+		let issuance = Balances::total_issuance();
+
+		assert_ok!(AppsModule::force_destroy_app(RuntimeOrigin::root(), AppRef::Hash(app_hash)));
+		System::assert_last_event(Event::<Test>::AppForceRemoved { app_hash, slashed: deposit }.into());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 1_000 - deposit);
+		assert_eq!(Balances::total_issuance(), issuance - deposit);
+		assert_eq!(AppsModule::app_deposit(app_hash), 0);
+	});
+}
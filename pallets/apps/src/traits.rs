@@ -0,0 +1,20 @@
+/// Hook used by other pallets to push a rating derived from reviews back into an app.
+pub trait AppRatingHandler<Hash, Star> {
+    /// Called whenever the aggregate rating of `app_hash` changes. `None` means the app has no
+    /// rating left.
+    fn on_rating_changed(app_hash: &Hash, star: Option<Star>);
+}
+
+impl<Hash, Star> AppRatingHandler<Hash, Star> for () {
+    fn on_rating_changed(_app_hash: &Hash, _star: Option<Star>) {}
+}
+
+/// Hook called once an app has been removed from storage, so dependent pallets can clean up
+/// what they keep for it.
+pub trait OnAppDestroyed<Hash> {
+    fn on_app_destroyed(app_hash: &Hash);
+}
+
+impl<Hash> OnAppDestroyed<Hash> for () {
+    fn on_app_destroyed(_app_hash: &Hash) {}
+}
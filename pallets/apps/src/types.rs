@@ -4,8 +4,64 @@ use codec::{Encode, MaxEncodedLen, Decode};
 use frame_support::pallet_prelude::BoundedVec;
 use scale_info::TypeInfo;
 use frame_support::traits::Time;
+use frame_support::RuntimeDebug;
 use crate::Config;
 
+/// What a maintainer is allowed to do on an app. The owner is always an `Admin`.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum MaintainerRole {
+    /// Can edit the app and manage maintainers.
+    Admin,
+    /// Can edit the app.
+    Editor,
+    /// Can answer reviews on behalf of the app.
+    Responder,
+}
+
+impl MaintainerRole {
+    pub fn can_edit(&self) -> bool {
+        matches!(self, MaintainerRole::Admin | MaintainerRole::Editor)
+    }
+
+    pub fn can_manage(&self) -> bool {
+        matches!(self, MaintainerRole::Admin)
+    }
+
+    pub fn can_reply(&self) -> bool {
+        matches!(self, MaintainerRole::Admin | MaintainerRole::Responder)
+    }
+}
+
+pub type CategoryId = u32;
+
+/// Identifies an app either by its hash or by its sequential id.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum AppRef<Hash, AppId> {
+    Hash(Hash),
+    Id(AppId),
+}
+
+pub type AppRefOf<T> = AppRef<<T as frame_system::Config>::Hash, <T as Config>::AppId>;
+
+/// Semantic version of a release, ordered by major, minor then patch.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct SemVer {
+    pub major: u16,
+    pub minor: u16,
+    pub patch: u16,
+}
+
+#[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+pub struct Release<T: Config> {
+    pub version: SemVer,
+    /// Hash of the published artifact.
+    pub artifact_hash: T::Hash,
+    /// Hash of the changelog content, which is stored off-chain.
+    pub changelog_hash: T::Hash,
+    pub published_at: T::Moment,
+}
+
 #[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen)]
 #[scale_info(skip_type_params(T))]
 pub struct App<T: Config> {
@@ -63,7 +119,11 @@ impl <T: Config> App<T> {
         self.symbol = symbol;
     }
 
-    pub fn set_star(&mut self, star: T::Star) { 
-        self.star = Some(star);
+    pub fn set_owner(&mut self, owner: T::AccountId) {
+        self.owner = owner;
+    }
+
+    pub fn set_star(&mut self, star: Option<T::Star>) {
+        self.star = star;
     }
 }
\ No newline at end of file
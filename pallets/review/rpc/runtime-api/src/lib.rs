@@ -0,0 +1,132 @@
+//! Runtime API of the review platform.
+//!
+//! Calls return plain DTOs so clients do not have to decode pallet storage. Whenever a DTO
+//! or a call changes, the API version is bumped so clients can tell which shape they get.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// Largest page the paginated calls return, bigger limits are clamped to it.
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// A page of items and the cursor to pass to get the next one, `None` on the last page.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct Page<Item, Cursor> {
+	pub items: Vec<Item>,
+	pub next: Option<Cursor>,
+}
+
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct AppInfo<AccountId, Hash, AppId, Moment, Star> {
+	pub id: AppId,
+	pub hash: Hash,
+	pub name: Vec<u8>,
+	pub symbol: Vec<u8>,
+	pub owner: AccountId,
+	pub created_at: Moment,
+	/// Star shown for the app, root override included.
+	pub star: Option<Star>,
+	pub archived: bool,
+}
+
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ReviewInfo<AccountId, Hash, Star> {
+	pub id: Hash,
+	pub app: Hash,
+	pub star: Option<Star>,
+	pub title: Vec<u8>,
+	pub cons: Vec<u8>,
+	pub pros: Vec<u8>,
+	pub owner: AccountId,
+	pub created_date: u64,
+	/// Pinned release as `(major, minor, patch)`.
+	pub release: Option<(u16, u16, u16)>,
+	/// Reviewer profile the review was written under.
+	pub reviewer: Option<Hash>,
+	/// Hidden by flags until a moderator looks at it, its star does not count.
+	pub hidden: bool,
+}
+
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ReviewerInfo<AccountId, Hash> {
+	pub id: Hash,
+	pub name: Vec<u8>,
+	/// Computed from the outcome of the owner's reviews.
+	pub reputation: u32,
+	pub owner: AccountId,
+}
+
+/// Rating of an app built from its reviews.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct RatingSummary {
+	pub count: u32,
+	/// Average star rounded to the nearest whole star, `None` without reviews.
+	pub average: Option<u32>,
+	/// Number of reviews per star, index `0` holds the 1-star reviews.
+	pub histogram: Vec<u32>,
+	/// Average in hundredths of a star where every review counts the same.
+	pub raw: Option<u32>,
+	/// Average in hundredths of a star where reviews count by the weight of their author.
+	pub weighted: Option<u32>,
+}
+
+/// A review with the helpfulness votes it received.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct RankedReview<AccountId, Hash, Star> {
+	pub review: ReviewInfo<AccountId, Hash, Star>,
+	pub helpful: u32,
+	pub unhelpful: u32,
+}
+
+/// Review and app events of a block, decoded by the runtime.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum PlatformEvent<AccountId, Hash, Star> {
+	ReviewCreated { review: Hash, app: Hash, author: AccountId, star: Star },
+	AppCreated { app: Hash },
+	AppUpdated { app: Hash },
+	AppStarUpdated { app: Hash, star: Option<Star> },
+}
+
+sp_api::decl_runtime_apis! {
+	#[api_version(1)]
+	pub trait ReviewPlatformApi<AccountId, Hash, AppId, Moment, Star> where
+		AccountId: Codec,
+		Hash: Codec,
+		AppId: Codec,
+		Moment: Codec,
+		Star: Codec,
+	{
+		fn app_by_id(app_id: AppId) -> Option<AppInfo<AccountId, Hash, AppId, Moment, Star>>;
+
+		fn apps_by_owner(owner: AccountId) -> Vec<AppInfo<AccountId, Hash, AppId, Moment, Star>>;
+
+		/// Apps ordered by id, starting at `cursor`.
+		fn apps_page(cursor: AppId, limit: u32) -> Page<AppInfo<AccountId, Hash, AppId, Moment, Star>, AppId>;
+
+		/// Reviews of an app in submission order, `cursor` is the position to start from.
+		fn reviews_for_app(app: Hash, cursor: u32, limit: u32) -> Page<ReviewInfo<AccountId, Hash, Star>, u32>;
+
+		fn reviews_by_account(account: AccountId) -> Vec<ReviewInfo<AccountId, Hash, Star>>;
+
+		fn reviewer(id: Hash) -> Option<ReviewerInfo<AccountId, Hash>>;
+
+		/// Rating of an app, `None` when the app does not exist.
+		fn rating_summary(app: Hash) -> Option<RatingSummary>;
+
+		/// Review and app events deposited in the block the call is made at.
+		fn platform_events() -> Vec<PlatformEvent<AccountId, Hash, Star>>;
+
+		/// Reviews of an app from most to least helpful, `cursor` is the position to start from.
+		fn reviews_by_helpfulness(app: Hash, cursor: u32, limit: u32) -> Page<RankedReview<AccountId, Hash, Star>, u32>;
+
+		/// Reputation of an account as a review author.
+		fn reputation(account: AccountId) -> u32;
+
+		/// Reviews written under a reviewer profile.
+		fn reviews_by_reviewer(reviewer: Hash) -> Vec<ReviewInfo<AccountId, Hash, Star>>;
+	}
+}
@@ -0,0 +1,499 @@
+//! JSON-RPC interface of the review platform, served under the `reviewPlatform` namespace.
+//!
+//! Every method reads through the `ReviewPlatformApi` runtime API at the given block, or at
+//! the best block when none is given. Byte fields are returned as UTF-8 strings.
+//!
+//! Subscriptions read the events of every new best block, or of every finalized block when
+//! `finalized` is set, and push the ones matching the subscription.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use futures::{future, stream, stream::BoxStream, FutureExt, StreamExt};
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::{
+		error::{CallError, ErrorObject},
+		SubscriptionResult,
+	},
+	SubscriptionSink,
+};
+use sc_client_api::BlockchainEvents;
+use sc_rpc::SubscriptionTaskExecutor;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_review_rpc_runtime_api::ReviewPlatformApi as ReviewPlatformRuntimeApi;
+use pallet_review_rpc_runtime_api::{AppInfo, PlatformEvent, ReviewInfo, ReviewerInfo};
+
+#[cfg(test)]
+mod tests;
+
+/// Page size used when a paginated call is made without a limit.
+pub const DEFAULT_PAGE_SIZE: u32 = 20;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct App<AccountId, Hash, AppId, Moment, Star> {
+	pub id: AppId,
+	pub hash: Hash,
+	pub name: String,
+	pub symbol: String,
+	pub owner: AccountId,
+	pub created_at: Moment,
+	pub star: Option<Star>,
+	pub archived: bool,
+}
+
+impl<AccountId, Hash, AppId, Moment, Star> From<AppInfo<AccountId, Hash, AppId, Moment, Star>>
+	for App<AccountId, Hash, AppId, Moment, Star>
+{
+	fn from(app: AppInfo<AccountId, Hash, AppId, Moment, Star>) -> Self {
+		App {
+			id: app.id,
+			hash: app.hash,
+			name: to_string(app.name),
+			symbol: to_string(app.symbol),
+			owner: app.owner,
+			created_at: app.created_at,
+			star: app.star,
+			archived: app.archived,
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Review<AccountId, Hash, Star> {
+	pub id: Hash,
+	pub app: Hash,
+	pub star: Option<Star>,
+	pub title: String,
+	pub cons: String,
+	pub pros: String,
+	pub owner: AccountId,
+	pub created_date: u64,
+	/// Pinned release formatted as `major.minor.patch`.
+	pub release: Option<String>,
+	pub reviewer: Option<Hash>,
+	pub hidden: bool,
+}
+
+impl<AccountId, Hash, Star> From<ReviewInfo<AccountId, Hash, Star>> for Review<AccountId, Hash, Star> {
+	fn from(review: ReviewInfo<AccountId, Hash, Star>) -> Self {
+		Review {
+			id: review.id,
+			app: review.app,
+			star: review.star,
+			title: to_string(review.title),
+			cons: to_string(review.cons),
+			pros: to_string(review.pros),
+			owner: review.owner,
+			created_date: review.created_date,
+			release: review.release.map(|(major, minor, patch)| format!("{}.{}.{}", major, minor, patch)),
+			reviewer: review.reviewer,
+			hidden: review.hidden,
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Reviewer<AccountId, Hash> {
+	pub id: Hash,
+	pub name: String,
+	pub reputation: u32,
+	pub owner: AccountId,
+}
+
+impl<AccountId, Hash> From<ReviewerInfo<AccountId, Hash>> for Reviewer<AccountId, Hash> {
+	fn from(reviewer: ReviewerInfo<AccountId, Hash>) -> Self {
+		Reviewer { id: reviewer.id, name: to_string(reviewer.name), reputation: reviewer.reputation, owner: reviewer.owner }
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RatingSummary {
+	pub count: u32,
+	pub average: Option<u32>,
+	/// Number of reviews per star, index `0` holds the 1-star reviews.
+	pub histogram: Vec<u32>,
+	/// Averages in hundredths of a star, `425` is 4.25 stars.
+	pub raw: Option<u32>,
+	pub weighted: Option<u32>,
+}
+
+impl From<pallet_review_rpc_runtime_api::RatingSummary> for RatingSummary {
+	fn from(summary: pallet_review_rpc_runtime_api::RatingSummary) -> Self {
+		RatingSummary {
+			count: summary.count,
+			average: summary.average,
+			histogram: summary.histogram,
+			raw: summary.raw,
+			weighted: summary.weighted,
+		}
+	}
+}
+
+/// A review created in `block`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewNotification<BlockHash, AccountId, Hash, Star> {
+	pub block: BlockHash,
+	pub review: Hash,
+	pub app: Hash,
+	pub author: AccountId,
+	pub star: Star,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum AppChange<Star> {
+	Created,
+	Updated,
+	StarUpdated { star: Option<Star> },
+}
+
+/// A change to `app` made in `block`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppNotification<BlockHash, Hash, Star> {
+	pub block: BlockHash,
+	pub app: Hash,
+	pub change: AppChange<Star>,
+}
+
+/// A page of items and the cursor of the next page, `None` on the last page.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Page<Item, Cursor> {
+	pub items: Vec<Item>,
+	pub next: Option<Cursor>,
+}
+
+impl<Item, Cursor> Page<Item, Cursor> {
+	fn from_runtime<RuntimeItem: Into<Item>>(page: pallet_review_rpc_runtime_api::Page<RuntimeItem, Cursor>) -> Self {
+		Page { items: page.items.into_iter().map(Into::into).collect(), next: page.next }
+	}
+}
+
+#[rpc(client, server)]
+pub trait ReviewPlatformApi<BlockHash, AccountId, Hash, AppId, Moment, Star> {
+	#[method(name = "reviewPlatform_app")]
+	fn app(&self, app_id: AppId, at: Option<BlockHash>) -> RpcResult<Option<App<AccountId, Hash, AppId, Moment, Star>>>;
+
+	#[method(name = "reviewPlatform_apps")]
+	fn apps(
+		&self,
+		cursor: Option<AppId>,
+		limit: Option<u32>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Page<App<AccountId, Hash, AppId, Moment, Star>, AppId>>;
+
+	#[method(name = "reviewPlatform_appsByOwner")]
+	fn apps_by_owner(
+		&self,
+		owner: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<App<AccountId, Hash, AppId, Moment, Star>>>;
+
+	#[method(name = "reviewPlatform_reviews")]
+	fn reviews(
+		&self,
+		app: Hash,
+		cursor: Option<u32>,
+		limit: Option<u32>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Page<Review<AccountId, Hash, Star>, u32>>;
+
+	#[method(name = "reviewPlatform_reviewsByAccount")]
+	fn reviews_by_account(&self, account: AccountId, at: Option<BlockHash>) -> RpcResult<Vec<Review<AccountId, Hash, Star>>>;
+
+	#[method(name = "reviewPlatform_reviewsByReviewer")]
+	fn reviews_by_reviewer(&self, reviewer: Hash, at: Option<BlockHash>) -> RpcResult<Vec<Review<AccountId, Hash, Star>>>;
+
+	#[method(name = "reviewPlatform_ratingSummary")]
+	fn rating_summary(&self, app: Hash, at: Option<BlockHash>) -> RpcResult<Option<RatingSummary>>;
+
+	#[method(name = "reviewPlatform_reviewer")]
+	fn reviewer(&self, id: Hash, at: Option<BlockHash>) -> RpcResult<Option<Reviewer<AccountId, Hash>>>;
+
+	#[method(name = "reviewPlatform_reputation")]
+	fn reputation(&self, account: AccountId, at: Option<BlockHash>) -> RpcResult<u32>;
+
+	/// New reviews, of `app` only when given.
+	#[subscription(
+		name = "reviewPlatform_subscribeReviews" => "reviewPlatform_review",
+		unsubscribe = "reviewPlatform_unsubscribeReviews",
+		item = ReviewNotification<BlockHash, AccountId, Hash, Star>,
+	)]
+	fn subscribe_reviews(&self, app: Option<Hash>, finalized: Option<bool>);
+
+	/// Apps created or updated and star changes, of `app` only when given.
+	#[subscription(
+		name = "reviewPlatform_subscribeAppChanges" => "reviewPlatform_appChange",
+		unsubscribe = "reviewPlatform_unsubscribeAppChanges",
+		item = AppNotification<BlockHash, Hash, Star>,
+	)]
+	fn subscribe_app_changes(&self, app: Option<Hash>, finalized: Option<bool>);
+}
+
+/// Provides the `reviewPlatform` RPC methods.
+pub struct ReviewPlatform<C, Block> {
+	client: Arc<C>,
+	executor: SubscriptionTaskExecutor,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> ReviewPlatform<C, Block> {
+	pub fn new(client: Arc<C>, executor: SubscriptionTaskExecutor) -> Self {
+		Self { client, executor, _marker: Default::default() }
+	}
+}
+
+impl<C, Block> ReviewPlatform<C, Block>
+where
+	Block: BlockT,
+	C: BlockchainEvents<Block>,
+{
+	/// Hashes of new best blocks, or of finalized blocks when `finalized` is set.
+	fn blocks(&self, finalized: bool) -> BoxStream<'static, Block::Hash> {
+		if finalized {
+			// one notification can finalize several blocks at once, the route lists those before
+			// the notified one
+			self.client
+				.finality_notification_stream()
+				.flat_map(|notification| {
+					let hashes: Vec<_> =
+						notification.tree_route.iter().cloned().chain(std::iter::once(notification.hash)).collect();
+					stream::iter(hashes)
+				})
+				.boxed()
+		} else {
+			self.client
+				.import_notification_stream()
+				.filter(|notification| future::ready(notification.is_new_best))
+				.map(|notification| notification.hash)
+				.boxed()
+		}
+	}
+
+	/// Forward `stream` to the subscriber until either side closes it.
+	fn pipe<T: Serialize + Send + 'static>(&self, mut sink: SubscriptionSink, stream: BoxStream<'static, T>) {
+		let fut = async move {
+			sink.pipe_from_stream(stream).await;
+		};
+		self.executor.spawn("review-platform-rpc-subscription", Some("rpc"), fut.boxed());
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to the runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+fn runtime_error(e: impl std::fmt::Debug) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(
+		Error::RuntimeError.into(),
+		"Unable to query the review platform.",
+		Some(format!("{:?}", e)),
+	))
+	.into()
+}
+
+/// Content was validated as UTF-8 on chain, anything else is replaced rather than rejected.
+fn to_string(bytes: Vec<u8>) -> String {
+	String::from_utf8(bytes).unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned())
+}
+
+impl<C, Block, AccountId, Hash, AppId, Moment, Star>
+	ReviewPlatformApiServer<<Block as BlockT>::Hash, AccountId, Hash, AppId, Moment, Star> for ReviewPlatform<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + BlockchainEvents<Block> + Send + Sync + 'static,
+	C::Api: ReviewPlatformRuntimeApi<Block, AccountId, Hash, AppId, Moment, Star>,
+	AccountId: Codec + Clone + Send + Sync + Serialize + DeserializeOwned + 'static,
+	Hash: Codec + Clone + PartialEq + Send + Sync + Serialize + DeserializeOwned + 'static,
+	AppId: Codec + Clone + Default + Send + Sync + Serialize + DeserializeOwned + 'static,
+	Moment: Codec + Clone + Send + Sync + Serialize + DeserializeOwned + 'static,
+	Star: Codec + Clone + Send + Sync + Serialize + DeserializeOwned + 'static,
+{
+	fn app(
+		&self,
+		app_id: AppId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<App<AccountId, Hash, AppId, Moment, Star>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let app = api.app_by_id(&at, app_id).map_err(runtime_error)?;
+		Ok(app.map(Into::into))
+	}
+
+	fn apps(
+		&self,
+		cursor: Option<AppId>,
+		limit: Option<u32>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Page<App<AccountId, Hash, AppId, Moment, Star>, AppId>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let page = api
+			.apps_page(&at, cursor.unwrap_or_default(), limit.unwrap_or(DEFAULT_PAGE_SIZE))
+			.map_err(runtime_error)?;
+		Ok(Page::from_runtime(page))
+	}
+
+	fn apps_by_owner(
+		&self,
+		owner: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<App<AccountId, Hash, AppId, Moment, Star>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let apps = api.apps_by_owner(&at, owner).map_err(runtime_error)?;
+		Ok(apps.into_iter().map(Into::into).collect())
+	}
+
+	fn reviews(
+		&self,
+		app: Hash,
+		cursor: Option<u32>,
+		limit: Option<u32>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Page<Review<AccountId, Hash, Star>, u32>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let page = api
+			.reviews_for_app(&at, app, cursor.unwrap_or_default(), limit.unwrap_or(DEFAULT_PAGE_SIZE))
+			.map_err(runtime_error)?;
+		Ok(Page::from_runtime(page))
+	}
+
+	fn reviews_by_account(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<Review<AccountId, Hash, Star>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let reviews = api.reviews_by_account(&at, account).map_err(runtime_error)?;
+		Ok(reviews.into_iter().map(Into::into).collect())
+	}
+
+	fn reviews_by_reviewer(
+		&self,
+		reviewer: Hash,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<Review<AccountId, Hash, Star>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let reviews = api.reviews_by_reviewer(&at, reviewer).map_err(runtime_error)?;
+		Ok(reviews.into_iter().map(Into::into).collect())
+	}
+
+	fn rating_summary(&self, app: Hash, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Option<RatingSummary>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let summary = api.rating_summary(&at, app).map_err(runtime_error)?;
+		Ok(summary.map(Into::into))
+	}
+
+	fn reviewer(&self, id: Hash, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Option<Reviewer<AccountId, Hash>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let reviewer = api.reviewer(&at, id).map_err(runtime_error)?;
+		Ok(reviewer.map(Into::into))
+	}
+
+	fn reputation(&self, account: AccountId, at: Option<<Block as BlockT>::Hash>) -> RpcResult<u32> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.reputation(&at, account).map_err(runtime_error)
+	}
+
+	fn subscribe_reviews(&self, sink: SubscriptionSink, app: Option<Hash>, finalized: Option<bool>) -> SubscriptionResult {
+		let client = self.client.clone();
+		let reviews = self
+			.blocks(finalized.unwrap_or(false))
+			.flat_map(move |block| {
+				let notifications: Vec<_> = events_at::<_, Block, AccountId, Hash, AppId, Moment, Star>(&*client, block)
+					.into_iter()
+					.filter_map(|event| match event {
+						PlatformEvent::ReviewCreated { review, app: app_hash, author, star }
+							if app.as_ref().map_or(true, |app| *app == app_hash) =>
+							Some(ReviewNotification { block, review, app: app_hash, author, star }),
+						_ => None,
+					})
+					.collect();
+				stream::iter(notifications)
+			})
+			.boxed();
+
+		self.pipe(sink, reviews);
+		Ok(())
+	}
+
+	fn subscribe_app_changes(&self, sink: SubscriptionSink, app: Option<Hash>, finalized: Option<bool>) -> SubscriptionResult {
+		let client = self.client.clone();
+		let changes = self
+			.blocks(finalized.unwrap_or(false))
+			.flat_map(move |block| {
+				let notifications: Vec<_> = events_at::<_, Block, AccountId, Hash, AppId, Moment, Star>(&*client, block)
+					.into_iter()
+					.filter_map(|event| match event {
+						PlatformEvent::AppCreated { app } => Some((app, AppChange::Created)),
+						PlatformEvent::AppUpdated { app } => Some((app, AppChange::Updated)),
+						PlatformEvent::AppStarUpdated { app, star } => Some((app, AppChange::StarUpdated { star })),
+						PlatformEvent::ReviewCreated { .. } => None,
+					})
+					.filter(|(app_hash, _)| app.as_ref().map_or(true, |app| app == app_hash))
+					.map(|(app, change)| AppNotification { block, app, change })
+					.collect();
+				stream::iter(notifications)
+			})
+			.boxed();
+
+		self.pipe(sink, changes);
+		Ok(())
+	}
+}
+
+/// Platform events of `block`. Blocks whose state can not be read anymore yield no event.
+fn events_at<C, Block, AccountId, Hash, AppId, Moment, Star>(
+	client: &C,
+	block: Block::Hash,
+) -> Vec<PlatformEvent<AccountId, Hash, Star>>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block>,
+	C::Api: ReviewPlatformRuntimeApi<Block, AccountId, Hash, AppId, Moment, Star>,
+	AccountId: Codec,
+	Hash: Codec,
+	AppId: Codec,
+	Moment: Codec,
+	Star: Codec,
+{
+	client.runtime_api().platform_events(&BlockId::hash(block)).unwrap_or_default()
+}
@@ -0,0 +1,324 @@
+use super::*;
+
+use jsonrpsee::{types::EmptyServerParams as EmptyParams, RpcModule};
+use pallet_review_rpc_runtime_api::{Page as RuntimePage, RankedReview, RatingSummary as RuntimeRatingSummary};
+use sc_block_builder::BlockBuilderProvider;
+use sc_client_api::{FinalityNotifications, ImportNotifications, StorageEventStream, StorageKey};
+use sp_api::ApiRef;
+use sp_blockchain::{BlockStatus, Info};
+use sp_consensus::BlockOrigin;
+use sp_core::{testing::TaskExecutor, H256};
+use sp_runtime::traits::{Header as HeaderT, NumberFor};
+use substrate_test_runtime_client::{runtime::Block, ClientBlockImportExt, TestClient};
+
+type AccountId = u64;
+type AppId = u32;
+type Moment = u64;
+type Star = u32;
+
+const APP: H256 = H256::repeat_byte(1);
+const REVIEWER: H256 = H256::repeat_byte(2);
+const OTHER_APP: H256 = H256::repeat_byte(3);
+
+/// Test client whose runtime API is served by `MockApi`.
+struct TestApi {
+	client: Arc<TestClient>,
+}
+
+struct MockApi;
+
+fn app_info() -> AppInfo<AccountId, H256, AppId, Moment, Star> {
+	AppInfo {
+		id: 0,
+		hash: APP,
+		name: b"Wallet".to_vec(),
+		symbol: b"WLT".to_vec(),
+		owner: 1,
+		created_at: 6000,
+		star: Some(4),
+		archived: false,
+	}
+}
+
+fn review_info(index: u32) -> ReviewInfo<AccountId, H256, Star> {
+	ReviewInfo {
+		id: H256::from_low_u64_be(index as u64),
+		app: APP,
+		star: Some(4),
+		title: b"Great".to_vec(),
+		cons: Vec::new(),
+		pros: b"Fast".to_vec(),
+		owner: 2 + index as u64,
+		created_date: 6000,
+		release: if index == 0 { Some((1, 2, 3)) } else { None },
+		reviewer: if index == 0 { Some(REVIEWER) } else { None },
+		hidden: index == 2,
+	}
+}
+
+sp_api::mock_impl_runtime_apis! {
+	impl ReviewPlatformRuntimeApi<Block, AccountId, H256, AppId, Moment, Star> for MockApi {
+		fn app_by_id(app_id: AppId) -> Option<AppInfo<AccountId, H256, AppId, Moment, Star>> {
+			if app_id == 0 { Some(app_info()) } else { None }
+		}
+
+		fn apps_by_owner(owner: AccountId) -> Vec<AppInfo<AccountId, H256, AppId, Moment, Star>> {
+			if owner == 1 { vec![app_info()] } else { Vec::new() }
+		}
+
+		fn apps_page(_cursor: AppId, _limit: u32) -> RuntimePage<AppInfo<AccountId, H256, AppId, Moment, Star>, AppId> {
+			RuntimePage { items: vec![app_info()], next: None }
+		}
+
+		fn reviews_for_app(app: H256, cursor: u32, limit: u32) -> RuntimePage<ReviewInfo<AccountId, H256, Star>, u32> {
+			if app != APP {
+				return RuntimePage { items: Vec::new(), next: None };
+			}
+			let end = (cursor + limit).min(3);
+			let items = (cursor..end).map(review_info).collect();
+			RuntimePage { items, next: if end < 3 { Some(end) } else { None } }
+		}
+
+		fn reviews_by_account(account: AccountId) -> Vec<ReviewInfo<AccountId, H256, Star>> {
+			(0..3).map(review_info).filter(|review| review.owner == account).collect()
+		}
+
+		fn reviewer(id: H256) -> Option<ReviewerInfo<AccountId, H256>> {
+			if id == REVIEWER {
+				Some(ReviewerInfo { id, name: b"Alice".to_vec(), reputation: 12, owner: 2 })
+			} else {
+				None
+			}
+		}
+
+		fn rating_summary(app: H256) -> Option<RuntimeRatingSummary> {
+			if app == APP {
+				Some(RuntimeRatingSummary {
+					count: 3,
+					average: Some(4),
+					histogram: vec![0, 0, 0, 3],
+					raw: Some(400),
+					weighted: Some(400),
+				})
+			} else {
+				None
+			}
+		}
+
+		fn platform_events() -> Vec<PlatformEvent<AccountId, H256, Star>> {
+			vec![
+				PlatformEvent::AppCreated { app: OTHER_APP },
+				PlatformEvent::ReviewCreated { review: H256::repeat_byte(4), app: OTHER_APP, author: 2, star: 2 },
+				PlatformEvent::ReviewCreated { review: H256::repeat_byte(5), app: APP, author: 3, star: 5 },
+				PlatformEvent::AppStarUpdated { app: APP, star: Some(5) },
+			]
+		}
+
+		fn reviews_by_helpfulness(_app: H256, _cursor: u32, _limit: u32) -> RuntimePage<RankedReview<AccountId, H256, Star>, u32> {
+			RuntimePage { items: Vec::new(), next: None }
+		}
+
+		fn reputation(account: AccountId) -> u32 {
+			if account == 2 { 12 } else { 0 }
+		}
+
+		fn reviews_by_reviewer(reviewer: H256) -> Vec<ReviewInfo<AccountId, H256, Star>> {
+			(0..3).map(review_info).filter(|review| review.reviewer == Some(reviewer)).collect()
+		}
+	}
+}
+
+impl ProvideRuntimeApi<Block> for TestApi {
+	type Api = MockApi;
+
+	fn runtime_api(&self) -> ApiRef<'_, Self::Api> {
+		MockApi.into()
+	}
+}
+
+impl HeaderBackend<Block> for TestApi {
+	fn header(&self, id: BlockId<Block>) -> sp_blockchain::Result<Option<<Block as BlockT>::Header>> {
+		self.client.header(id)
+	}
+
+	fn info(&self) -> Info<Block> {
+		self.client.info()
+	}
+
+	fn status(&self, id: BlockId<Block>) -> sp_blockchain::Result<BlockStatus> {
+		self.client.status(id)
+	}
+
+	fn number(&self, hash: <Block as BlockT>::Hash) -> sp_blockchain::Result<Option<NumberFor<Block>>> {
+		self.client.number(hash)
+	}
+
+	fn hash(&self, number: NumberFor<Block>) -> sp_blockchain::Result<Option<<Block as BlockT>::Hash>> {
+		self.client.hash(number)
+	}
+}
+
+impl BlockchainEvents<Block> for TestApi {
+	fn import_notification_stream(&self) -> ImportNotifications<Block> {
+		self.client.import_notification_stream()
+	}
+
+	fn finality_notification_stream(&self) -> FinalityNotifications<Block> {
+		self.client.finality_notification_stream()
+	}
+
+	fn storage_changes_notification_stream(
+		&self,
+		filter_keys: Option<&[StorageKey]>,
+		child_filter_keys: Option<&[(StorageKey, Option<Vec<StorageKey>>)]>,
+	) -> sp_blockchain::Result<StorageEventStream<<Block as BlockT>::Hash>> {
+		self.client.storage_changes_notification_stream(filter_keys, child_filter_keys)
+	}
+}
+
+fn test_api() -> Arc<TestApi> {
+	Arc::new(TestApi { client: Arc::new(substrate_test_runtime_client::new()) })
+}
+
+fn rpc_with(api: Arc<TestApi>) -> RpcModule<ReviewPlatform<TestApi, Block>> {
+	ReviewPlatform::new(api, Arc::new(TaskExecutor::new())).into_rpc()
+}
+
+fn rpc() -> RpcModule<ReviewPlatform<TestApi, Block>> {
+	rpc_with(test_api())
+}
+
+/// Import an empty block on top of the best block, as final when `finalized` is set.
+async fn import_block(api: &TestApi, finalized: bool) -> H256 {
+	let mut client = api.client.clone();
+	let block = client.new_block(Default::default()).unwrap().build().unwrap().block;
+	let hash = block.header.hash();
+	if finalized {
+		client.import_as_final(BlockOrigin::Own, block).await.unwrap();
+	} else {
+		client.import(BlockOrigin::Own, block).await.unwrap();
+	}
+	hash
+}
+
+#[tokio::test]
+async fn app_is_returned_with_string_fields() {
+	let rpc = rpc();
+
+	let app: Option<App<AccountId, H256, AppId, Moment, Star>> =
+		rpc.call("reviewPlatform_app", [0u32]).await.unwrap();
+	let app = app.unwrap();
+	assert_eq!(app.name, "Wallet");
+	assert_eq!(app.symbol, "WLT");
+	assert_eq!(app.star, Some(4));
+
+	let missing: Option<App<AccountId, H256, AppId, Moment, Star>> =
+		rpc.call("reviewPlatform_app", [7u32]).await.unwrap();
+	assert_eq!(missing, None);
+}
+
+#[tokio::test]
+async fn app_can_be_queried_at_a_block() {
+	let api = test_api();
+	let genesis = api.info().genesis_hash;
+	let rpc = rpc_with(api);
+
+	let app: Option<App<AccountId, H256, AppId, Moment, Star>> =
+		rpc.call("reviewPlatform_app", (0u32, Some(genesis))).await.unwrap();
+	assert_eq!(app.map(|app| app.hash), Some(APP));
+}
+
+#[tokio::test]
+async fn reviews_are_paginated() {
+	let rpc = rpc();
+
+	let page: Page<Review<AccountId, H256, Star>, u32> =
+		rpc.call("reviewPlatform_reviews", (APP, 0u32, 2u32)).await.unwrap();
+	assert_eq!(page.items.len(), 2);
+	assert_eq!(page.items[0].release, Some("1.2.3".to_string()));
+	assert_eq!(page.items[0].pros, "Fast");
+	assert_eq!(page.next, Some(2));
+
+	let page: Page<Review<AccountId, H256, Star>, u32> =
+		rpc.call("reviewPlatform_reviews", (APP, 2u32, 2u32)).await.unwrap();
+	assert_eq!(page.items.len(), 1);
+	assert!(page.items[0].hidden);
+	assert_eq!(page.next, None);
+}
+
+#[tokio::test]
+async fn rating_summary_works() {
+	let rpc = rpc();
+
+	let summary: Option<RatingSummary> = rpc.call("reviewPlatform_ratingSummary", [APP]).await.unwrap();
+	assert_eq!(
+		summary,
+		Some(RatingSummary {
+			count: 3,
+			average: Some(4),
+			histogram: vec![0, 0, 0, 3],
+			raw: Some(400),
+			weighted: Some(400),
+		})
+	);
+
+	let missing: Option<RatingSummary> = rpc.call("reviewPlatform_ratingSummary", [H256::zero()]).await.unwrap();
+	assert_eq!(missing, None);
+}
+
+#[tokio::test]
+async fn reviewer_profile_works() {
+	let rpc = rpc();
+
+	let reviewer: Option<Reviewer<AccountId, H256>> = rpc.call("reviewPlatform_reviewer", [REVIEWER]).await.unwrap();
+	assert_eq!(reviewer.map(|reviewer| reviewer.name), Some("Alice".to_string()));
+
+	let reputation: u32 = rpc.call("reviewPlatform_reputation", [2u64]).await.unwrap();
+	assert_eq!(reputation, 12);
+
+	let reviews: Vec<Review<AccountId, H256, Star>> = rpc.call("reviewPlatform_reviewsByReviewer", [REVIEWER]).await.unwrap();
+	assert_eq!(reviews.len(), 1);
+	assert_eq!(reviews[0].reviewer, Some(REVIEWER));
+}
+
+#[tokio::test]
+async fn review_subscription_filters_by_app() {
+	let api = test_api();
+	let rpc = rpc_with(api.clone());
+
+	let mut sub = rpc.subscribe("reviewPlatform_subscribeReviews", [Some(APP)]).await.unwrap();
+	let block = import_block(&api, false).await;
+
+	let (review, _) = sub.next::<ReviewNotification<H256, AccountId, H256, Star>>().await.unwrap().unwrap();
+	assert_eq!(review, ReviewNotification { block, review: H256::repeat_byte(5), app: APP, author: 3, star: 5 });
+}
+
+#[tokio::test]
+async fn app_subscription_can_follow_finalized_blocks() {
+	let api = test_api();
+	let rpc = rpc_with(api.clone());
+
+	let mut sub = rpc
+		.subscribe("reviewPlatform_subscribeAppChanges", (None::<H256>, Some(true)))
+		.await
+		.unwrap();
+	let block = import_block(&api, true).await;
+
+	let (created, _) = sub.next::<AppNotification<H256, H256, Star>>().await.unwrap().unwrap();
+	assert_eq!(created, AppNotification { block, app: OTHER_APP, change: AppChange::Created });
+	let (star, _) = sub.next::<AppNotification<H256, H256, Star>>().await.unwrap().unwrap();
+	assert_eq!(star.change, AppChange::StarUpdated { star: Some(5) });
+}
+
+#[tokio::test]
+async fn review_subscription_without_filter_gets_every_review() {
+	let api = test_api();
+	let rpc = rpc_with(api.clone());
+
+	let mut sub = rpc.subscribe("reviewPlatform_subscribeReviews", EmptyParams::new()).await.unwrap();
+	import_block(&api, false).await;
+
+	let (first, _) = sub.next::<ReviewNotification<H256, AccountId, H256, Star>>().await.unwrap().unwrap();
+	let (second, _) = sub.next::<ReviewNotification<H256, AccountId, H256, Star>>().await.unwrap().unwrap();
+	assert_eq!((first.app, second.app), (OTHER_APP, APP));
+}
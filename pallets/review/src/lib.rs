@@ -3,15 +3,28 @@
 pub use pallet::*;
 pub use pallet_apps;
 
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub mod migrations;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_support::traits::Time;
 	use frame_system::{pallet_prelude::*};
 	use frame_support::inherent::Vec;
+	use frame_support::traits::{Currency, Imbalance, OnUnbalanced, ReservableCurrency};
+	use pallet_apps::{AppRatingHandler, OnAppDestroyed, SemVer, BalanceOf, AppRefOf};
+	use review_primitives::validation::{validate_text, TextError};
+	use review_primitives::reputation::{RatingWeight, ReputationHandler, ReviewOutcome};
+	use review_primitives::profiles::{OnReviewerRemoved, ReviewerProfiles};
 
 
-	use frame_support::sp_runtime::{traits::{AtLeast32Bit, Scale, CheckedAdd, Hash}, SaturatedConversion};
+	use frame_support::sp_runtime::{traits::{AtLeast32Bit, Scale, CheckedAdd, Hash, Saturating, Zero}, Perbill, SaturatedConversion};
 
 
 	#[cfg(feature = "std")]
@@ -29,7 +42,246 @@ pub mod pallet {
 		pub pros: BoundedVec<u8, T::ContentLimit>,
 		pub owner: T::AccountId,
 		pub created_date: u64,
+		/// Release of the app this review is about, if the author pinned one.
+		pub release: Option<SemVer>,
+		/// Reviewer profile of the author the review was written under.
+		pub reviewer: Option<T::Hash>,
+	}
+
+	/// Rating aggregate of an app, built from the stars of its reviews.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct RatingAggregate<T: Config> {
+		pub count: u32,
+		pub sum: u64,
+		/// Number of reviews per star, index `0` holds the 1-star reviews.
+		pub histogram: BoundedVec<u32, <T as pallet_apps::Config>::StarLimit>,
+	}
+
+	impl<T: Config> Default for RatingAggregate<T> {
+		fn default() -> Self {
+			RatingAggregate { count: 0, sum: 0, histogram: Default::default() }
+		}
+	}
+
+	impl<T: Config> RatingAggregate<T> {
+		pub fn add(&mut self, star: u32) -> Result<(), Error<T>> {
+			ensure!(star >= 1, <Error<T>>::StarLimited);
+
+			while (self.histogram.len() as u32) < star {
+				self.histogram.try_push(0).map_err(|_| <Error<T>>::StarLimited)?;
+			}
+
+			let slot = self.histogram.get_mut((star - 1) as usize).ok_or(<Error<T>>::StarLimited)?;
+			*slot = slot.checked_add(1).ok_or(<Error<T>>::StorageOverflow)?;
+			self.count = self.count.checked_add(1).ok_or(<Error<T>>::StorageOverflow)?;
+			self.sum = self.sum.checked_add(star as u64).ok_or(<Error<T>>::StorageOverflow)?;
+
+			Ok(())
+		}
+
+		pub fn remove(&mut self, star: u32) -> Result<(), Error<T>> {
+			ensure!(star >= 1, <Error<T>>::StarLimited);
+
+			let slot = self.histogram.get_mut((star - 1) as usize).ok_or(<Error<T>>::StorageOverflow)?;
+			*slot = slot.checked_sub(1).ok_or(<Error<T>>::StorageOverflow)?;
+			self.count = self.count.checked_sub(1).ok_or(<Error<T>>::StorageOverflow)?;
+			self.sum = self.sum.checked_sub(star as u64).ok_or(<Error<T>>::StorageOverflow)?;
+
+			Ok(())
+		}
+
+		/// Average star rounded to the nearest whole star, `None` when there is no review.
+		pub fn average(&self) -> Option<u32> {
+			if self.count == 0 {
+				return None;
+			}
+			let count = self.count as u64;
+			Some(((self.sum + count / 2) / count) as u32)
+		}
+
+		/// Average star in hundredths of a star, `None` when there is no review.
+		pub fn precise_average(&self) -> Option<u32> {
+			hundredths(self.sum, self.count as u64)
+		}
+	}
+
+	/// Stars of the reviews of an app, each multiplied by the weight of its author.
+	#[derive(Clone, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct WeightedAggregate {
+		pub weight: u64,
+		pub sum: u64,
+	}
+
+	impl WeightedAggregate {
+		pub fn add<T: Config>(&mut self, star: u32, weight: u32) -> Result<(), Error<T>> {
+			self.weight = self.weight.checked_add(weight as u64).ok_or(<Error<T>>::StorageOverflow)?;
+			self.sum = self.sum.checked_add(star as u64 * weight as u64).ok_or(<Error<T>>::StorageOverflow)?;
+			Ok(())
+		}
+
+		pub fn remove<T: Config>(&mut self, star: u32, weight: u32) -> Result<(), Error<T>> {
+			self.weight = self.weight.checked_sub(weight as u64).ok_or(<Error<T>>::StorageOverflow)?;
+			self.sum = self.sum.checked_sub(star as u64 * weight as u64).ok_or(<Error<T>>::StorageOverflow)?;
+			Ok(())
+		}
+
+		/// Weighted average star in hundredths of a star, `None` when nothing is weighted.
+		pub fn average(&self) -> Option<u32> {
+			hundredths(self.sum, self.weight)
+		}
+	}
+
+	/// Averages of an app in hundredths of a star, so `425` is 4.25 stars.
+	#[derive(Clone, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct RatingAverages {
+		/// Every review counts the same.
+		pub raw: Option<u32>,
+		/// Reviews count by the `RatingWeight` of their author.
+		pub weighted: Option<u32>,
+	}
+
+	fn hundredths(sum: u64, count: u64) -> Option<u32> {
+		if count == 0 {
+			return None;
+		}
+		Some(((sum.saturating_mul(100) + count / 2) / count).saturated_into())
+	}
+
+	/// Earlier version of an edited review.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Revision<T: Config> {
+		/// Number of the edit that replaced this version, the first edit is `1`.
+		pub number: u32,
+		/// Hash of the title, cons and pros of this version.
+		pub content_hash: T::Hash,
+		pub star: Option<<T as pallet_apps::Config>::Star>,
+		/// When this version was replaced.
+		pub edited_at: u64,
+	}
+
+	/// Public answer of the app team to a review.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Reply<T: Config> {
+		pub author: T::AccountId,
+		pub content: BoundedVec<u8, T::ReplyLimit>,
+		pub created_at: u64,
+		pub edited_at: Option<u64>,
+		/// Star of the review when the reply was posted.
+		pub star_at_reply: Option<<T as pallet_apps::Config>::Star>,
+		/// Whether the reviewer changed their star since the reply was posted. Once set it stays
+		/// set, even if the star is later edited back.
+		pub rating_changed_after_reply: bool,
+	}
+
+	/// Sequential id of a comment within the thread of a review.
+	pub type CommentId = u32;
+
+	/// Comment in the discussion under a review.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Comment<T: Config> {
+		pub author: T::AccountId,
+		/// Comment this one answers, `None` for a comment on the review itself.
+		pub parent: Option<CommentId>,
+		/// Nesting level, `0` for a comment on the review itself.
+		pub depth: u32,
+		pub content: BoundedVec<u8, T::CommentLimit>,
+		pub created_at: u64,
+		pub edited_at: Option<u64>,
+	}
+
+	pub type NegativeImbalanceOf<T> = <<T as pallet_apps::Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::NegativeImbalance;
+
+	#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum BondStatus {
+		/// Reserved until the challenge period is over.
+		Locked,
+		/// Given back to the author.
+		Released,
+		/// Taken by moderation within the challenge period.
+		Slashed,
+	}
+
+	/// Bond an author puts up for a review, at stake while the review can be challenged.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Bond<T: Config> {
+		pub owner: T::AccountId,
+		pub amount: BalanceOf<T>,
+		/// Block from which the bond can be claimed back.
+		pub unlock_at: T::BlockNumber,
+		pub status: BondStatus,
+		/// Whether the author was credited for the review standing through a challenge period.
+		/// Later periods started by edits earn nothing more.
+		pub matured: bool,
+	}
+
+	#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum FlagReason {
+		Spam,
+		Abuse,
+		OffTopic,
+		/// Written by someone who did not use the app, or paid for.
+		Fake,
+		Other,
+	}
+
+	#[derive(Clone, Copy, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum ReviewStatus {
+		#[default]
+		Visible,
+		/// Flagged by enough accounts, waiting for moderation. Hidden reviews do not count
+		/// toward ratings.
+		Hidden,
+	}
+
+	#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum Vote {
+		Helpful,
+		Unhelpful,
+	}
+
+	/// Helpfulness votes received by a review.
+	#[derive(Clone, Copy, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct VoteTally {
+		pub helpful: u32,
+		pub unhelpful: u32,
+	}
+
+	impl VoteTally {
+		fn slot(&mut self, vote: Vote) -> &mut u32 {
+			match vote {
+				Vote::Helpful => &mut self.helpful,
+				Vote::Unhelpful => &mut self.unhelpful,
+			}
+		}
+
+		/// Helpful votes minus unhelpful ones.
+		pub fn score(&self) -> i64 {
+			self.helpful as i64 - self.unhelpful as i64
+		}
+	}
+
+	/// What is left of a review its author retracted with a tombstone.
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Tombstone<T: Config> {
+		pub app_id: T::Hash,
+		pub owner: T::AccountId,
+		pub retracted_at: u64,
 	}
+
 	#[derive(Clone, Encode, Decode, PartialEq, Copy, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 	pub enum Gender {
@@ -37,8 +289,12 @@ pub mod pallet {
 		Female,
 	}
 
+	/// Version 1 links reviews to a release and a reviewer profile, and indexes them per author.
+	pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
@@ -49,6 +305,10 @@ pub mod pallet {
 
 		type ContentLimit: Get<u32>;
 
+		/// Minimum length of a review title. Cons and pros may be left empty.
+		#[pallet::constant]
+		type ContentMinLimit: Get<u32>;
+
 		// type Star: Parameter
 		// 	+ Default
 		// 	+ MaxEncodedLen
@@ -88,6 +348,94 @@ pub mod pallet {
 
 
 		type ReviewTime: Time;
+
+		/// Receives the average star of an app every time one of its reviews changes it.
+		type RatingHandler: AppRatingHandler<Self::Hash, <Self as pallet_apps::Config>::Star>;
+
+		/// Deposit reserved for every review, on top of the per-byte deposit.
+		#[pallet::constant]
+		type ReviewDepositBase: Get<BalanceOf<Self>>;
+
+		/// Deposit reserved per byte of review title, cons and pros.
+		#[pallet::constant]
+		type ReviewDepositPerByte: Get<BalanceOf<Self>>;
+
+		/// Origin allowed to remove abusive reviews.
+		type ModeratorOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Maximum number of reviews of a destroyed app removed by a single `reap_reviews` call.
+		#[pallet::constant]
+		type ReapBatchSize: Get<u32>;
+
+		/// Number of earlier versions kept for each review, the oldest is dropped first.
+		#[pallet::constant]
+		type MaxRevisions: Get<u32>;
+
+		/// Maximum number of accounts that can vote on a review.
+		#[pallet::constant]
+		type MaxVotesPerReview: Get<u32>;
+
+		/// Maximum length of a reply to a review.
+		#[pallet::constant]
+		type ReplyLimit: Get<u32>;
+
+		/// Maximum length of a comment.
+		#[pallet::constant]
+		type CommentLimit: Get<u32>;
+
+		/// Deepest nesting level of a comment, comments on the review itself are at level `0`.
+		#[pallet::constant]
+		type MaxCommentDepth: Get<u32>;
+
+		/// Maximum number of comments under a review.
+		#[pallet::constant]
+		type MaxCommentsPerReview: Get<u32>;
+
+		/// Length in blocks of the window comment rate limits are counted over.
+		#[pallet::constant]
+		type CommentRateWindow: Get<Self::BlockNumber>;
+
+		/// Maximum number of comments an account can post within one window.
+		#[pallet::constant]
+		type MaxCommentsPerWindow: Get<u32>;
+
+		/// Number of flags that hides a review until a moderator looks at it.
+		#[pallet::constant]
+		type FlagThreshold: Get<u32>;
+
+		/// Bond reserved for every review on top of its deposit.
+		#[pallet::constant]
+		type ReviewBond: Get<BalanceOf<Self>>;
+
+		/// Number of blocks during which moderation can slash the bond of a review.
+		#[pallet::constant]
+		type ChallengePeriod: Get<Self::BlockNumber>;
+
+		/// Maximum number of bonds given back at the start of a block. Bonds past it are given
+		/// back in the next blocks.
+		#[pallet::constant]
+		type MaxBondUnlocksPerBlock: Get<u32>;
+
+		/// Share of a slashed bond split between the accounts that flagged the review.
+		#[pallet::constant]
+		type FlaggerReward: Get<Perbill>;
+
+		/// Receives the rest of slashed bonds.
+		type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Told about votes, maturity and removal of reviews to keep the reputation of authors.
+		type Reputation: ReputationHandler<Self::AccountId>;
+
+		/// Weight of the author of a review in the weighted rating of apps.
+		type RatingWeight: RatingWeight<Self::AccountId>;
+
+		/// Reviewer profiles reviews can be written under.
+		type Reviewers: ReviewerProfiles<Self::AccountId, Self::Hash>;
+
+		/// Maximum number of reviews written under a reviewer profile, which are all detached from
+		/// it at once when it is removed.
+		#[pallet::constant]
+		type MaxReviewsPerReviewer: Get<u32>;
 	
 	}
 
@@ -101,6 +449,17 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn review_of)]
+	/// Active review of each account for each app, keyed by account then app hash.
+	pub(super) type AccountAppReviews<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Twox64Concat, T::Hash, T::Hash, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn review_nonce)]
+	/// Number of reviews created so far, review ids are derived from it.
+	pub(super) type ReviewNonce<T> = StorageValue<_, u64, ValueQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn reviews)]
 	pub(super) type Reviews<T: Config> = StorageMap<_, Twox64Concat, T::Hash, Review<T>>;
@@ -113,15 +472,175 @@ pub mod pallet {
 	pub(super) type ReviewsOwnedByApp<T: Config> =
 		StorageMap<_, Twox64Concat, T::Hash, BoundedVec<T::Hash, T::ReviewOwnerByAppLimit>, ValueQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn app_ratings)]
+	/// Rating aggregate of each app, keyed by app hash.
+	pub(super) type AppRatings<T: Config> =
+		StorageMap<_, Twox64Concat, T::Hash, RatingAggregate<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn release_ratings)]
+	/// Rating aggregate of each release of an app, built from the reviews pinned to it.
+	pub(super) type ReleaseRatings<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, SemVer, RatingAggregate<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn weighted_ratings)]
+	/// Weighted rating aggregate of each app, keyed by app hash.
+	pub(super) type WeightedRatings<T: Config> = StorageMap<_, Twox64Concat, T::Hash, WeightedAggregate, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn rating_weight)]
+	/// Weight each counted review was added with, so it is removed with the same one.
+	pub(super) type ReviewWeights<T: Config> = StorageMap<_, Twox64Concat, T::Hash, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn rating_averages)]
+	/// Raw and weighted average of each app.
+	pub(super) type AppRatingAverages<T: Config> = StorageMap<_, Twox64Concat, T::Hash, RatingAverages, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn reviewer_review)]
+	/// Reviews written under each reviewer profile, keyed by profile then review.
+	pub(super) type ReviewsByReviewer<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, T::Hash, (), OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn reviewer_review_count)]
+	/// Number of reviews written under each reviewer profile.
+	pub(super) type ReviewerReviewCount<T: Config> = StorageMap<_, Twox64Concat, T::Hash, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn review_deposit)]
+	/// Deposit reserved from the author of each review.
+	pub(super) type ReviewDeposits<T: Config> = StorageMap<_, Twox64Concat, T::Hash, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn review_revisions)]
+	/// Earlier versions of each edited review, oldest first.
+	pub(super) type ReviewRevisions<T: Config> =
+		StorageMap<_, Twox64Concat, T::Hash, BoundedVec<Revision<T>, T::MaxRevisions>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn review_vote)]
+	/// Vote of each account on each review, keyed by review then account.
+	pub(super) type ReviewVotes<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::Hash, Blake2_128Concat, T::AccountId, Vote, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn review_tally)]
+	/// Votes received by each review.
+	pub(super) type ReviewTallies<T: Config> = StorageMap<_, Twox64Concat, T::Hash, VoteTally, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn reply)]
+	/// Reply of the app team to each review.
+	pub(super) type Replies<T: Config> = StorageMap<_, Twox64Concat, T::Hash, Reply<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn comment)]
+	/// Comments under each review, keyed by review then comment id so a thread can be paged.
+	pub(super) type Comments<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::Hash, Twox64Concat, CommentId, Comment<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_comment_id)]
+	pub(super) type NextCommentId<T: Config> = StorageMap<_, Twox64Concat, T::Hash, CommentId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn comment_count)]
+	/// Number of comments currently under each review.
+	pub(super) type CommentCount<T: Config> = StorageMap<_, Twox64Concat, T::Hash, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn comment_window)]
+	/// Start of the current rate limit window of each account and the comments posted in it.
+	pub(super) type CommentWindows<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (T::BlockNumber, u32), OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn review_flag)]
+	/// Flag of each account on each review, keyed by review then account.
+	pub(super) type ReviewFlags<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::Hash, Blake2_128Concat, T::AccountId, FlagReason, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn flag_count)]
+	pub(super) type FlagCount<T: Config> = StorageMap<_, Twox64Concat, T::Hash, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn review_status)]
+	pub(super) type ReviewStatuses<T: Config> = StorageMap<_, Twox64Concat, T::Hash, ReviewStatus, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn review_bond)]
+	/// Bond of each review. The bond of a review removed by moderation is kept, telling whether
+	/// it was slashed or given back.
+	pub(super) type ReviewBonds<T: Config> = StorageMap<_, Twox64Concat, T::Hash, Bond<T>, OptionQuery>;
+
+	#[pallet::storage]
+	/// Reviews whose bond unlocks at a block, keyed by block then review id. Their bonds are
+	/// given back when that block starts.
+	pub(super) type BondUnlocks<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Twox64Concat, T::Hash, (), OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn unlock_cursor)]
+	/// Oldest block whose bond unlocks may not all be processed yet.
+	pub(super) type UnlockCursor<T: Config> = StorageValue<_, T::BlockNumber, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn retracted_review)]
+	/// Reviews retracted by their author with a tombstone.
+	pub(super) type RetractedReviews<T: Config> = StorageMap<_, Twox64Concat, T::Hash, Tombstone<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn pending_reaps)]
+	/// Destroyed apps whose reviews are still waiting to be removed.
+	pub(super) type PendingReaps<T: Config> = StorageMap<_, Twox64Concat, T::Hash, (), OptionQuery>;
+
 
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/main-docs/build/events-errors/
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		/// Event documentation should end with an array that provides descriptive names for event
-		/// parameters. [something, who]
-		ReviewCreated { who: T::AccountId },
+		/// A review was created. [who, review_id, app_hash, star]
+		ReviewCreated { who: T::AccountId, review_id: T::Hash, app_hash: T::Hash, star: <T as pallet_apps::Config>::Star },
+		/// Reviews of a destroyed app were removed. [app_hash, count, remaining]
+		ReviewsReaped { app_hash: T::Hash, count: u32, remaining: u32 },
+		/// A review was edited by its author. [review_id, revision]
+		ReviewEdited { review_id: T::Hash, revision: u32 },
+		/// A review was retracted by its author and its deposit refunded. [review_id, tombstone]
+		ReviewRetracted { review_id: T::Hash, tombstone: bool },
+		/// An account voted on a review. [review_id, who, vote]
+		ReviewVoted { review_id: T::Hash, who: T::AccountId, vote: Vote },
+		/// The app team replied to a review. [review_id, who]
+		ReplyPosted { review_id: T::Hash, who: T::AccountId },
+		/// A reply was edited. [review_id, who]
+		ReplyEdited { review_id: T::Hash, who: T::AccountId },
+		/// A reply was deleted. [review_id, who]
+		ReplyDeleted { review_id: T::Hash, who: T::AccountId },
+		/// A comment was posted under a review. [review_id, comment_id, who]
+		CommentPosted { review_id: T::Hash, comment_id: CommentId, who: T::AccountId },
+		/// A comment was edited by its author. [review_id, comment_id]
+		CommentEdited { review_id: T::Hash, comment_id: CommentId },
+		/// A comment was deleted by its author. [review_id, comment_id]
+		CommentDeleted { review_id: T::Hash, comment_id: CommentId },
+		/// A comment was removed by moderation. [review_id, comment_id]
+		CommentModerated { review_id: T::Hash, comment_id: CommentId },
+		/// A review was flagged. [review_id, who, reason]
+		ReviewFlagged { review_id: T::Hash, who: T::AccountId, reason: FlagReason },
+		/// A review reached the flag threshold and was hidden. [review_id]
+		ReviewHidden { review_id: T::Hash },
+		/// A moderator made a hidden review visible again or dismissed its flags. [review_id]
+		ReviewRestored { review_id: T::Hash },
+		/// The bond of a review went back to its author. [review_id, amount]
+		BondReleased { review_id: T::Hash, amount: BalanceOf<T> },
+		/// The bond of a review was slashed by moderation. [review_id, amount, to_flaggers]
+		BondSlashed { review_id: T::Hash, amount: BalanceOf<T>, to_flaggers: BalanceOf<T> },
+		/// A review was removed by moderation and its deposit slashed. [review_id, slashed]
+		ReviewRemoved { review_id: T::Hash, slashed: BalanceOf<T> },
 	}
 
 	// Errors inform users that something went wrong.
@@ -143,6 +662,143 @@ pub mod pallet {
 		ReviewOwnerLimit,
 
 		ReviewOwnerByAppLimit,
+
+		AppArchived,
+
+		NothingToReap,
+
+		ReleaseNotExist,
+
+		InsufficientDeposit,
+
+		EmptyField,
+
+		InvalidUtf8,
+
+		ContentTooShort,
+
+		ContentTooLong,
+
+		/// The account already has a review of this app, which has to be edited instead.
+		AlreadyReviewed,
+
+		NotReviewOwner,
+
+		/// The derived review id is already in use.
+		ReviewIdTaken,
+
+		/// Authors and app maintainers can not vote on the review.
+		CanNotVoteOwnReview,
+
+		/// The account already cast this vote on the review.
+		AlreadyVoted,
+
+		/// The review has received as many votes as it can hold.
+		TooManyVotes,
+
+		/// Only the app owner and its admins and responders can reply to reviews.
+		CanNotReply,
+
+		AlreadyReplied,
+
+		ReplyNotExist,
+
+		CommentNotExist,
+
+		NotCommentAuthor,
+
+		/// The comment would be nested deeper than `MaxCommentDepth`.
+		CommentTooDeep,
+
+		/// The review reached `MaxCommentsPerReview`.
+		TooManyComments,
+
+		/// The account posted `MaxCommentsPerWindow` comments in the current window.
+		CommentRateLimited,
+
+		/// Authors and app maintainers can not flag the review.
+		CanNotFlagOwnReview,
+
+		AlreadyFlagged,
+
+		/// The review is neither hidden nor flagged.
+		ReviewNotHidden,
+
+		InsufficientBond,
+
+		/// Flagged and hidden reviews wait for moderation and can not be retracted.
+		ReviewUnderModeration,
+
+		NoLockedBond,
+
+		/// The challenge period of the review is not over yet.
+		BondStillLocked,
+
+		/// The reviewer profile does not exist or belongs to someone else.
+		NotReviewerOwner,
+
+		/// The reviewer profile reached `MaxReviewsPerReviewer`.
+		TooManyReviewerReviews,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			let db_weight = T::DbWeight::get();
+			let mut block = Self::unlock_cursor().unwrap_or(n);
+			// every block looked at counts against the limit, so catching up stays bounded too
+			let mut budget = T::MaxBondUnlocksPerBlock::get();
+			let (mut blocks, mut count) = (0u64, 0u64);
+
+			while block <= n && budget > 0 {
+				let review_ids: Vec<T::Hash> =
+					<BondUnlocks<T>>::iter_key_prefix(block).take(budget as usize).collect();
+				for review_id in &review_ids {
+					<BondUnlocks<T>>::remove(block, review_id);
+					// bonds still under moderation or already settled are skipped
+					let _ = Self::mature_bond(review_id);
+				}
+				blocks += 1;
+				count += review_ids.len() as u64;
+
+				let taken = review_ids.len() as u32;
+				if taken < budget {
+					block = block.saturating_add(1u32.into());
+				}
+				budget = budget.saturating_sub(taken.max(1));
+			}
+			<UnlockCursor<T>>::put(block);
+
+			db_weight
+				.reads_writes(1 + blocks, 1)
+				.saturating_add(Self::bond_maturation_weight().saturating_mul(count))
+		}
+
+		fn on_idle(_n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			let db_weight = T::DbWeight::get();
+			// finding the app and writing back its review list
+			let base_weight = db_weight.reads_writes(2, 2);
+			let review_weight = Self::review_removal_weight();
+
+			if remaining_weight.ref_time() <= base_weight.ref_time() {
+				return Weight::zero();
+			}
+
+			let app_hash = match <PendingReaps<T>>::iter_keys().next() {
+				Some(app_hash) => app_hash,
+				None => return db_weight.reads(1),
+			};
+
+			let budget = (remaining_weight.ref_time() - base_weight.ref_time()) / review_weight.ref_time().max(1);
+			let limit = budget.min(T::ReapBatchSize::get() as u64) as u32;
+			if limit == 0 {
+				return db_weight.reads(1);
+			}
+
+			let (count, _) = Self::reap(&app_hash, limit);
+
+			base_weight.saturating_add(review_weight.saturating_mul(count as u64))
+		}
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -151,38 +807,59 @@ pub mod pallet {
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 
-		#[pallet::weight(100)]
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(14, 10).ref_time() + Pallet::<T>::rating_change_weight().ref_time())]
 		pub fn create_review(
 			origin: OriginFor<T>,
 			star: <T as pallet_apps::Config>::Star,
-			app_id: T::Hash,
+			app: AppRefOf<T>,
 			title: Vec<u8>,
 			cons: Vec<u8>,
 			pros: Vec<u8>,
+			release: Option<SemVer>,
+			reviewer: Option<T::Hash>,
 		) -> DispatchResult {
 			
 			let who = ensure_signed(origin)?;
 
-			let mut app = <pallet_apps::Module<T>>::apps(&app_id).ok_or(<Error<T>>::AppNotExist)?;
+			// Reviews can only be written under profiles of the author
+			if let Some(reviewer) = &reviewer {
+				ensure!(T::Reviewers::is_owner(&who, reviewer), <Error<T>>::NotReviewerOwner);
+				ensure!(
+					Self::reviewer_review_count(reviewer) < T::MaxReviewsPerReviewer::get(),
+					<Error<T>>::TooManyReviewerReviews
+				);
+			}
+
+			let app_id = <pallet_apps::Pallet<T>>::resolve_app(&app).map_err(|_| <Error<T>>::AppNotExist)?;
+			ensure!(<pallet_apps::Module<T>>::apps(&app_id).is_some(), <Error<T>>::AppNotExist);
+
+			// Pinned release must be part of the app history
+			if let Some(version) = &release {
+				ensure!(<pallet_apps::Pallet<T>>::release_of(&app_id, version).is_some(), <Error<T>>::ReleaseNotExist);
+			}
+
+			// Archived apps are read-only
+			ensure!(!<pallet_apps::Pallet<T>>::is_archived(&app_id), <Error<T>>::AppArchived);
+
+			// One active review per app for each user
+			ensure!(!<AccountAppReviews<T>>::contains_key(&who, &app_id), <Error<T>>::AlreadyReviewed);
 
 			// Limit review of each user
 			let review_owned = <ReviewOnwer<T>>::get(&who);
 			ensure!((review_owned.len() as u32) < T::ReviewOwnerLimit::get(), <Error<T>>::ReviewOwnerLimit);
 
-			// App owner can not create review
-			ensure!(who != app.owner(), <Error<T>>::OwnerCanNotReview);
+			// App owner and maintainers can not create review
+			ensure!(!<pallet_apps::Pallet<T>>::is_maintainer(&app_id, &who), <Error<T>>::OwnerCanNotReview);
 
-			let bounded_title: BoundedVec<u8, T::ContentLimit> =
-				title.clone().try_into().expect("title is too long");
-			let bounded_cons: BoundedVec<u8, T::ContentLimit> =
-				cons.clone().try_into().expect("content is too long");
-			let bounded_pros: BoundedVec<u8, T::ContentLimit> =
-				pros.clone().try_into().expect("content is too long");
+			let bounded_title = Self::bounded_content(title, T::ContentMinLimit::get())?;
+			let bounded_cons = Self::bounded_content(cons, 0)?;
+			let bounded_pros = Self::bounded_content(pros, 0)?;
 
 			let star_limit = <T as pallet_apps::Config>::StarLimit::get();
 
-			// ensure star less than star limit
-			ensure!(star <= star_limit.into(), <Error<T>>::StarLimited);
+			// ensure star is between 1 and star limit
+			ensure!(star >= 1_u32.into() && star <= star_limit.into(), <Error<T>>::StarLimited);
 
 			let now = T::ReviewTime::now().saturated_into();
 
@@ -193,18 +870,486 @@ pub mod pallet {
 				cons: bounded_cons,
 				pros: bounded_pros,
 				owner: who.clone(),
-				created_date: now
+				created_date: now,
+				release,
+				reviewer,
 			};
 
-			let review_id = T::Hashing::hash_of(&review);
+			// ids come from a counter, so identical reviews never share one
+			let nonce = Self::review_nonce();
+			let review_id = Self::review_id_of(&who, nonce);
+			ensure!(
+				!<Reviews<T>>::contains_key(&review_id) && !<RetractedReviews<T>>::contains_key(&review_id),
+				<Error<T>>::ReviewIdTaken
+			);
+			<ReviewNonce<T>>::put(nonce.checked_add(1).ok_or(<Error<T>>::StorageOverflow)?);
+
+			let deposit = Self::deposit_for(&review);
+			T::Currency::reserve(&who, deposit).map_err(|_| <Error<T>>::InsufficientDeposit)?;
+			<ReviewDeposits<T>>::insert(&review_id, deposit);
+
+			Self::lock_bond(&review_id, &who)?;
 
+			if let Some(reviewer) = &reviewer {
+				<ReviewsByReviewer<T>>::insert(reviewer, &review_id, ());
+				<ReviewerReviewCount<T>>::mutate(reviewer, |count| *count = count.saturating_add(1));
+			}
 			<Reviews<T>>::insert(&review_id, review);
 
 			ReviewsOwnedByApp::<T>::try_mutate(&app_id, |review_vec| {
 				review_vec.try_push(review_id)
 			}).map_err(|_| <Error<T>>::ReviewOwnerByAppLimit)?;
 
-			Self::deposit_event(Event::ReviewCreated { who });
+			ReviewOnwer::<T>::try_mutate(&who, |review_vec| {
+				review_vec.try_push(review_id)
+			}).map_err(|_| <Error<T>>::ReviewOwnerLimit)?;
+			<AccountAppReviews<T>>::insert(&who, &app_id, review_id);
+
+			Self::add_rating(&review_id, &who, &app_id, release, star.into())?;
+
+			Self::deposit_event(Event::ReviewCreated { who, review_id, app_hash: app_id, star });
+
+			Ok(())
+		}
+
+		/// Change the star and content of a review. The replaced version is kept in its revision
+		/// history. The edited review can be challenged again: its bond is locked, or reserved
+		/// again once given back, for a new challenge period.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(8, 9).ref_time() + 2 * Pallet::<T>::rating_change_weight().ref_time())]
+		pub fn edit_review(
+			origin: OriginFor<T>,
+			review_id: T::Hash,
+			star: <T as pallet_apps::Config>::Star,
+			title: Vec<u8>,
+			cons: Vec<u8>,
+			pros: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let review = <Reviews<T>>::get(&review_id).ok_or(<Error<T>>::ReviewNotExist)?;
+			ensure!(review.owner == who, <Error<T>>::NotReviewOwner);
+
+			let app_id = review.app_id;
+			ensure!(<pallet_apps::Pallet<T>>::apps(&app_id).is_some(), <Error<T>>::AppNotExist);
+			ensure!(!<pallet_apps::Pallet<T>>::is_archived(&app_id), <Error<T>>::AppArchived);
+
+			let star_limit = <T as pallet_apps::Config>::StarLimit::get();
+			ensure!(star >= 1_u32.into() && star <= star_limit.into(), <Error<T>>::StarLimited);
+
+			let edited = Review::<T> {
+				star: Some(star),
+				title: Self::bounded_content(title, T::ContentMinLimit::get())?,
+				cons: Self::bounded_content(cons, 0)?,
+				pros: Self::bounded_content(pros, 0)?,
+				..review.clone()
+			};
+
+			// the deposit follows the size of the content
+			let old_deposit = Self::review_deposit(&review_id);
+			let new_deposit = Self::deposit_for(&edited);
+			if new_deposit > old_deposit {
+				T::Currency::reserve(&who, new_deposit - old_deposit).map_err(|_| <Error<T>>::InsufficientDeposit)?;
+			} else {
+				T::Currency::unreserve(&who, old_deposit - new_deposit);
+			}
+			<ReviewDeposits<T>>::insert(&review_id, new_deposit);
+
+			Self::lock_bond(&review_id, &who)?;
+
+			if review.star != edited.star && Self::counts_toward_rating(&review_id, &app_id) {
+				if let Some(old_star) = review.star {
+					Self::remove_rating(&review_id, &app_id, review.release, old_star.into())?;
+				}
+				Self::add_rating(&review_id, &who, &app_id, review.release, star.into())?;
+			}
+
+			<Replies<T>>::mutate(&review_id, |reply_option| {
+				if let Some(reply) = reply_option {
+					reply.rating_changed_after_reply |= reply.star_at_reply != edited.star;
+				}
+			});
+
+			let revision = <ReviewRevisions<T>>::try_mutate(&review_id, |revisions| -> Result<u32, Error<T>> {
+				let number = revisions.last().map_or(1, |revision| revision.number.saturating_add(1));
+				// drop the oldest revision once the history is full
+				if revisions.len() as u32 >= T::MaxRevisions::get() && !revisions.is_empty() {
+					revisions.remove(0);
+				}
+				revisions.try_push(Revision::<T> {
+					number,
+					content_hash: T::Hashing::hash_of(&(&review.title, &review.cons, &review.pros)),
+					star: review.star,
+					edited_at: T::ReviewTime::now().saturated_into(),
+				}).map_err(|_| <Error<T>>::StorageOverflow)?;
+				Ok(number)
+			})?;
+
+			<Reviews<T>>::insert(&review_id, edited);
+
+			Self::deposit_event(Event::ReviewEdited { review_id, revision });
+
+			Ok(())
+		}
+
+		/// Withdraw a review and get its deposit back. The bond stays locked until the end of the
+		/// challenge period and is then given back. Flagged reviews can not be withdrawn, so they
+		/// can not escape moderation. With `tombstone` set, a
+		/// record of the retracted review is kept so it can be told apart from one that never
+		/// existed.
+		#[pallet::call_index(2)]
+		#[pallet::weight(Pallet::<T>::review_removal_weight().ref_time())]
+		pub fn retract_review(origin: OriginFor<T>, review_id: T::Hash, tombstone: bool) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let review = <Reviews<T>>::get(&review_id).ok_or(<Error<T>>::ReviewNotExist)?;
+			ensure!(review.owner == who, <Error<T>>::NotReviewOwner);
+			ensure!(
+				Self::review_status(&review_id) == ReviewStatus::Visible && Self::flag_count(&review_id) == 0,
+				<Error<T>>::ReviewUnderModeration
+			);
+
+			if let (Some(star), true) = (review.star, Self::counts_toward_rating(&review_id, &review.app_id)) {
+				Self::remove_rating(&review_id, &review.app_id, review.release, star.into())?;
+			}
+
+			<Reviews<T>>::remove(&review_id);
+			ReviewsOwnedByApp::<T>::mutate(&review.app_id, |review_vec| {
+				review_vec.retain(|id| *id != review_id)
+			});
+			Self::remove_author_index(&review_id, &review);
+			Self::remove_review_data(&review_id, &who);
+
+			T::Currency::unreserve(&who, <ReviewDeposits<T>>::take(&review_id));
+
+			if tombstone {
+				<RetractedReviews<T>>::insert(&review_id, Tombstone::<T> {
+					app_id: review.app_id,
+					owner: who,
+					retracted_at: T::ReviewTime::now().saturated_into(),
+				});
+			}
+
+			Self::deposit_event(Event::ReviewRetracted { review_id, tombstone });
+
+			Ok(())
+		}
+
+		/// Vote on how helpful a review is. Each account has one vote per review, which can be
+		/// changed later.
+		#[pallet::call_index(3)]
+		#[pallet::weight(Pallet::<T>::vote_weight().ref_time())]
+		pub fn vote_review(origin: OriginFor<T>, review_id: T::Hash, vote: Vote) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let review = <Reviews<T>>::get(&review_id).ok_or(<Error<T>>::ReviewNotExist)?;
+			ensure!(
+				review.owner != who && !<pallet_apps::Pallet<T>>::is_maintainer(&review.app_id, &who),
+				<Error<T>>::CanNotVoteOwnReview
+			);
+
+			let previous = <ReviewVotes<T>>::get(&review_id, &who);
+			ensure!(previous != Some(vote), <Error<T>>::AlreadyVoted);
+
+			<ReviewTallies<T>>::try_mutate(&review_id, |tally| -> Result<(), Error<T>> {
+				if let Some(previous) = previous {
+					let slot = tally.slot(previous);
+					*slot = slot.checked_sub(1).ok_or(<Error<T>>::StorageOverflow)?;
+				} else {
+					// every voter counts once in the tally
+					ensure!(tally.helpful.saturating_add(tally.unhelpful) < T::MaxVotesPerReview::get(), <Error<T>>::TooManyVotes);
+				}
+				let slot = tally.slot(vote);
+				*slot = slot.checked_add(1).ok_or(<Error<T>>::StorageOverflow)?;
+				Ok(())
+			})?;
+			<ReviewVotes<T>>::insert(&review_id, &who, vote);
+
+			if let Some(previous) = previous {
+				T::Reputation::note_outcome(&review.owner, ReviewOutcome::VoteWithdrawn { helpful: previous == Vote::Helpful });
+			}
+			T::Reputation::note_outcome(&review.owner, ReviewOutcome::Voted { helpful: vote == Vote::Helpful });
+
+			Self::deposit_event(Event::ReviewVoted { review_id, who, vote });
+
+			Ok(())
+		}
+
+		/// Publicly answer a review on behalf of its app. One reply is allowed per review.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(6, 1).ref_time())]
+		pub fn reply_to_review(origin: OriginFor<T>, review_id: T::Hash, content: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let review = <Reviews<T>>::get(&review_id).ok_or(<Error<T>>::ReviewNotExist)?;
+			Self::ensure_can_reply(&review.app_id, &who)?;
+			ensure!(!<Replies<T>>::contains_key(&review_id), <Error<T>>::AlreadyReplied);
+
+			let reply = Reply::<T> {
+				author: who.clone(),
+				content: Self::bounded_content(content, 1)?,
+				created_at: T::ReviewTime::now().saturated_into(),
+				edited_at: None,
+				star_at_reply: review.star,
+				rating_changed_after_reply: false,
+			};
+			<Replies<T>>::insert(&review_id, reply);
+
+			Self::deposit_event(Event::ReplyPosted { review_id, who });
+
+			Ok(())
+		}
+
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(6, 1).ref_time())]
+		pub fn edit_reply(origin: OriginFor<T>, review_id: T::Hash, content: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let review = <Reviews<T>>::get(&review_id).ok_or(<Error<T>>::ReviewNotExist)?;
+			Self::ensure_can_reply(&review.app_id, &who)?;
+
+			let content = Self::bounded_content(content, 1)?;
+			let now = T::ReviewTime::now().saturated_into();
+			<Replies<T>>::try_mutate(&review_id, |reply_option| -> Result<(), Error<T>> {
+				let reply = reply_option.as_mut().ok_or(<Error<T>>::ReplyNotExist)?;
+				reply.content = content;
+				reply.edited_at = Some(now);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::ReplyEdited { review_id, who });
+
+			Ok(())
+		}
+
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(6, 1).ref_time())]
+		pub fn delete_reply(origin: OriginFor<T>, review_id: T::Hash) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let review = <Reviews<T>>::get(&review_id).ok_or(<Error<T>>::ReviewNotExist)?;
+			Self::ensure_can_reply(&review.app_id, &who)?;
+			<Replies<T>>::take(&review_id).ok_or(<Error<T>>::ReplyNotExist)?;
+
+			Self::deposit_event(Event::ReplyDeleted { review_id, who });
+
+			Ok(())
+		}
+
+		/// Comment on a review, or answer the comment `parent` of its thread.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(6, 4).ref_time())]
+		pub fn post_comment(
+			origin: OriginFor<T>,
+			review_id: T::Hash,
+			parent: Option<CommentId>,
+			content: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let review = <Reviews<T>>::get(&review_id).ok_or(<Error<T>>::ReviewNotExist)?;
+			ensure!(!<pallet_apps::Pallet<T>>::is_archived(&review.app_id), <Error<T>>::AppArchived);
+			ensure!(Self::comment_count(&review_id) < T::MaxCommentsPerReview::get(), <Error<T>>::TooManyComments);
+
+			let depth = match parent {
+				Some(parent_id) => {
+					let parent = Self::comment(&review_id, parent_id).ok_or(<Error<T>>::CommentNotExist)?;
+					parent.depth.saturating_add(1)
+				},
+				None => 0,
+			};
+			ensure!(depth <= T::MaxCommentDepth::get(), <Error<T>>::CommentTooDeep);
+
+			let content = Self::bounded_content(content, 1)?;
+			Self::note_comment(&who)?;
+
+			let comment_id = Self::next_comment_id(&review_id);
+			<NextCommentId<T>>::insert(&review_id, comment_id.checked_add(1).ok_or(<Error<T>>::StorageOverflow)?);
+			<CommentCount<T>>::mutate(&review_id, |count| *count = count.saturating_add(1));
+			<Comments<T>>::insert(&review_id, comment_id, Comment::<T> {
+				author: who.clone(),
+				parent,
+				depth,
+				content,
+				created_at: T::ReviewTime::now().saturated_into(),
+				edited_at: None,
+			});
+
+			Self::deposit_event(Event::CommentPosted { review_id, comment_id, who });
+
+			Ok(())
+		}
+
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1).ref_time())]
+		pub fn edit_comment(origin: OriginFor<T>, review_id: T::Hash, comment_id: CommentId, content: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let content = Self::bounded_content(content, 1)?;
+			let now = T::ReviewTime::now().saturated_into();
+			<Comments<T>>::try_mutate(&review_id, comment_id, |comment_option| -> Result<(), Error<T>> {
+				let comment = comment_option.as_mut().ok_or(<Error<T>>::CommentNotExist)?;
+				ensure!(comment.author == who, <Error<T>>::NotCommentAuthor);
+				comment.content = content;
+				comment.edited_at = Some(now);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::CommentEdited { review_id, comment_id });
+
+			Ok(())
+		}
+
+		/// Delete a comment. Answers to it stay in the thread.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 2).ref_time())]
+		pub fn delete_comment(origin: OriginFor<T>, review_id: T::Hash, comment_id: CommentId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let comment = Self::comment(&review_id, comment_id).ok_or(<Error<T>>::CommentNotExist)?;
+			ensure!(comment.author == who, <Error<T>>::NotCommentAuthor);
+			Self::remove_comment(&review_id, comment_id);
+
+			Self::deposit_event(Event::CommentDeleted { review_id, comment_id });
+
+			Ok(())
+		}
+
+		/// Remove an abusive comment.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 2).ref_time())]
+		pub fn moderate_comment(origin: OriginFor<T>, review_id: T::Hash, comment_id: CommentId) -> DispatchResult {
+			T::ModeratorOrigin::ensure_origin(origin)?;
+
+			ensure!(<Comments<T>>::contains_key(&review_id, comment_id), <Error<T>>::CommentNotExist);
+			Self::remove_comment(&review_id, comment_id);
+
+			Self::deposit_event(Event::CommentModerated { review_id, comment_id });
+
+			Ok(())
+		}
+
+		/// Report a review to moderation. Once `FlagThreshold` accounts flagged it, the review is
+		/// hidden until a moderator restores or removes it.
+		#[pallet::call_index(11)]
+		#[pallet::weight(
+			T::DbWeight::get().reads_writes(6, 3).ref_time() + Pallet::<T>::rating_change_weight().ref_time()
+		)]
+		pub fn flag_review(origin: OriginFor<T>, review_id: T::Hash, reason: FlagReason) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let review = <Reviews<T>>::get(&review_id).ok_or(<Error<T>>::ReviewNotExist)?;
+			ensure!(
+				review.owner != who && !<pallet_apps::Pallet<T>>::is_maintainer(&review.app_id, &who),
+				<Error<T>>::CanNotFlagOwnReview
+			);
+			// a hidden review waits for a moderator, so flags stop at `FlagThreshold`
+			ensure!(Self::review_status(&review_id) == ReviewStatus::Visible, <Error<T>>::ReviewUnderModeration);
+			ensure!(!<ReviewFlags<T>>::contains_key(&review_id, &who), <Error<T>>::AlreadyFlagged);
+
+			let flags = Self::flag_count(&review_id).checked_add(1).ok_or(<Error<T>>::StorageOverflow)?;
+			<FlagCount<T>>::insert(&review_id, flags);
+			<ReviewFlags<T>>::insert(&review_id, &who, reason);
+
+			Self::deposit_event(Event::ReviewFlagged { review_id, who, reason });
+
+			if flags >= T::FlagThreshold::get() {
+				if let (Some(star), true) = (review.star, Self::counts_toward_rating(&review_id, &review.app_id)) {
+					Self::remove_rating(&review_id, &review.app_id, review.release, star.into())?;
+				}
+				<ReviewStatuses<T>>::insert(&review_id, ReviewStatus::Hidden);
+
+				Self::deposit_event(Event::ReviewHidden { review_id });
+			}
+
+			Ok(())
+		}
+
+		/// Make a hidden review visible again, or dismiss the flags of a review that is not hidden
+		/// yet, and clear its flags.
+		#[pallet::call_index(12)]
+		#[pallet::weight(
+			T::DbWeight::get().reads_writes(3 + T::FlagThreshold::get() as u64, 2 + T::FlagThreshold::get() as u64).ref_time()
+				+ Pallet::<T>::rating_change_weight().ref_time()
+				+ Pallet::<T>::bond_maturation_weight().ref_time()
+		)]
+		pub fn restore_review(origin: OriginFor<T>, review_id: T::Hash) -> DispatchResult {
+			T::ModeratorOrigin::ensure_origin(origin)?;
+
+			let review = <Reviews<T>>::get(&review_id).ok_or(<Error<T>>::ReviewNotExist)?;
+			let hidden = Self::review_status(&review_id) == ReviewStatus::Hidden;
+			ensure!(hidden || Self::flag_count(&review_id) > 0, <Error<T>>::ReviewNotHidden);
+
+			<ReviewStatuses<T>>::remove(&review_id);
+			Self::clear_flags(&review_id);
+			if let (Some(star), true) = (review.star, hidden && Self::counts_toward_rating(&review_id, &review.app_id)) {
+				Self::add_rating(&review_id, &review.owner, &review.app_id, review.release, star.into())?;
+			}
+			// the challenge period may have ended while the review was hidden
+			let _ = Self::mature_bond(&review_id);
+
+			Self::deposit_event(Event::ReviewRestored { review_id });
+
+			Ok(())
+		}
+
+		/// Give the bond of a review back to its author once the challenge period is over.
+		/// Bonds are given back as the period ends, this catches up on any that were skipped.
+		/// Anyone can call it.
+		#[pallet::call_index(13)]
+		#[pallet::weight(Pallet::<T>::bond_maturation_weight().ref_time())]
+		pub fn claim_bond(origin: OriginFor<T>, review_id: T::Hash) -> DispatchResult {
+			let _who = ensure_signed(origin)?;
+
+			Self::mature_bond(&review_id)?;
+
+			Ok(())
+		}
+
+		/// Remove an abusive review and slash the deposit of its author. Within the challenge
+		/// period the bond of the review is slashed as well.
+		#[pallet::call_index(14)]
+		#[pallet::weight(Pallet::<T>::review_removal_weight().ref_time())]
+		pub fn remove_review(origin: OriginFor<T>, review_id: T::Hash) -> DispatchResult {
+			T::ModeratorOrigin::ensure_origin(origin)?;
+
+			let review = <Reviews<T>>::take(&review_id).ok_or(<Error<T>>::ReviewNotExist)?;
+
+			if let (Some(star), true) = (review.star, Self::counts_toward_rating(&review_id, &review.app_id)) {
+				Self::remove_rating(&review_id, &review.app_id, review.release, star.into())?;
+			}
+
+			// the settled bond stays on record
+			Self::settle_bond(&review_id);
+			T::Reputation::note_outcome(&review.owner, ReviewOutcome::Removed);
+
+			ReviewsOwnedByApp::<T>::mutate(&review.app_id, |review_vec| {
+				review_vec.retain(|id| *id != review_id)
+			});
+			Self::remove_author_index(&review_id, &review);
+			Self::remove_review_data(&review_id, &review.owner);
+
+			let deposit = <ReviewDeposits<T>>::take(&review_id);
+			let (_, unslashed) = T::Currency::slash_reserved(&review.owner, deposit);
+
+			Self::deposit_event(Event::ReviewRemoved { review_id, slashed: deposit - unslashed });
+
+			Ok(())
+		}
+
+		/// Remove up to `ReapBatchSize` reviews of a destroyed app. Anyone can call it. The app is
+		/// referenced by hash only, as its id is released once it is destroyed.
+		#[pallet::call_index(15)]
+		#[pallet::weight(
+			T::DbWeight::get().reads_writes(2, 2).ref_time()
+				+ Pallet::<T>::review_removal_weight().ref_time() * T::ReapBatchSize::get() as u64
+		)]
+		pub fn reap_reviews(origin: OriginFor<T>, app_hash: T::Hash) -> DispatchResult {
+			let _who = ensure_signed(origin)?;
+
+			ensure!(<PendingReaps<T>>::contains_key(&app_hash), <Error<T>>::NothingToReap);
+
+			Self::reap(&app_hash, T::ReapBatchSize::get());
 
 			Ok(())
 		}
@@ -231,4 +1376,426 @@ pub mod pallet {
 		// 	Ok(reviews)
 		// }
 	}
+
+	impl<T: Config> Pallet<T> {
+		/// Remove at most `limit` reviews of a destroyed app, returning how many were removed
+		/// and how many are left.
+		fn reap(app_hash: &T::Hash, limit: u32) -> (u32, u32) {
+			let mut review_ids = <ReviewsOwnedByApp<T>>::get(app_hash);
+			let remaining = review_ids.len().saturating_sub(limit as usize);
+
+			for review_id in review_ids.iter().skip(remaining) {
+				if let Some(review) = <Reviews<T>>::take(review_id) {
+					if let Some(version) = review.release {
+						<ReleaseRatings<T>>::remove(app_hash, version);
+					}
+					Self::remove_author_index(review_id, &review);
+					Self::release_bond(review_id);
+					<ReviewBonds<T>>::remove(review_id);
+					Self::remove_review_data(review_id, &review.owner);
+					// the app is gone, authors get their deposit and bond back
+					T::Currency::unreserve(&review.owner, <ReviewDeposits<T>>::take(review_id));
+				}
+			}
+			let count = (review_ids.len() - remaining) as u32;
+			review_ids.truncate(remaining);
+
+			let remaining = review_ids.len() as u32;
+			if remaining == 0 {
+				<ReviewsOwnedByApp<T>>::remove(app_hash);
+				<PendingReaps<T>>::remove(app_hash);
+			} else {
+				<ReviewsOwnedByApp<T>>::insert(app_hash, review_ids);
+			}
+
+			Self::deposit_event(Event::ReviewsReaped { app_hash: *app_hash, count, remaining });
+
+			(count, remaining)
+		}
+
+		/// Id of the review created by `who` when the review counter is at `nonce`.
+		pub fn review_id_of(who: &T::AccountId, nonce: u64) -> T::Hash {
+			T::Hashing::hash_of(&(b"review", who, nonce))
+		}
+
+		/// `who` may answer reviews of the app, which must still be open.
+		fn ensure_can_reply(app_hash: &T::Hash, who: &T::AccountId) -> DispatchResult {
+			ensure!(<pallet_apps::Pallet<T>>::apps(app_hash).is_some(), <Error<T>>::AppNotExist);
+			ensure!(
+				<pallet_apps::Pallet<T>>::role_of(app_hash, who).map_or(false, |role| role.can_reply()),
+				<Error<T>>::CanNotReply
+			);
+			ensure!(!<pallet_apps::Pallet<T>>::is_archived(app_hash), <Error<T>>::AppArchived);
+			Ok(())
+		}
+
+		/// Count a comment of `who` against their rate limit, opening a new window when the
+		/// current one is over.
+		fn note_comment(who: &T::AccountId) -> DispatchResult {
+			let now = <frame_system::Pallet<T>>::block_number();
+			let (start, count) = match Self::comment_window(who) {
+				Some((start, count)) if now < start.saturating_add(T::CommentRateWindow::get()) => (start, count),
+				_ => (now, 0),
+			};
+			ensure!(count < T::MaxCommentsPerWindow::get(), <Error<T>>::CommentRateLimited);
+			<CommentWindows<T>>::insert(who, (start, count + 1));
+			Ok(())
+		}
+
+		fn remove_comment(review_id: &T::Hash, comment_id: CommentId) {
+			<Comments<T>>::remove(review_id, comment_id);
+			<CommentCount<T>>::mutate(review_id, |count| *count = count.saturating_sub(1));
+		}
+
+		/// Up to `limit` comments under a review in posting order, starting at id `cursor`, and the
+		/// id to resume from when more may follow.
+		pub fn comments_page(
+			review_id: &T::Hash,
+			cursor: CommentId,
+			limit: u32,
+		) -> (Vec<(CommentId, Comment<T>)>, Option<CommentId>) {
+			let end = Self::next_comment_id(review_id);
+			let mut comment_id = cursor;
+			let mut comments = Vec::new();
+			while comment_id < end && (comments.len() as u32) < limit {
+				if let Some(comment) = Self::comment(review_id, comment_id) {
+					comments.push((comment_id, comment));
+				}
+				comment_id += 1;
+			}
+			let next = if comment_id < end { Some(comment_id) } else { None };
+			(comments, next)
+		}
+
+		/// Whether the star of a review is part of the rating of its app. Hidden reviews are left
+		/// out, and ratings of a destroyed app are already gone.
+		fn counts_toward_rating(review_id: &T::Hash, app_hash: &T::Hash) -> bool {
+			Self::review_status(review_id) == ReviewStatus::Visible && !<PendingReaps<T>>::contains_key(app_hash)
+		}
+
+		fn clear_flags(review_id: &T::Hash) {
+			let _ = <ReviewFlags<T>>::clear_prefix(review_id, T::FlagThreshold::get(), None);
+			<FlagCount<T>>::remove(review_id);
+		}
+
+		/// Put the bond of a review at stake for a new challenge period, reserving it from `who`
+		/// unless it is still locked.
+		fn lock_bond(review_id: &T::Hash, who: &T::AccountId) -> DispatchResult {
+			let previous = Self::review_bond(review_id);
+			let matured = previous.as_ref().map_or(false, |bond| bond.matured);
+			let amount = match previous {
+				Some(bond) if bond.status == BondStatus::Locked => {
+					<BondUnlocks<T>>::remove(bond.unlock_at, review_id);
+					bond.amount
+				},
+				_ => {
+					let amount = T::ReviewBond::get();
+					T::Currency::reserve(who, amount).map_err(|_| <Error<T>>::InsufficientBond)?;
+					amount
+				},
+			};
+			let unlock_at = <frame_system::Pallet<T>>::block_number().saturating_add(T::ChallengePeriod::get());
+			<ReviewBonds<T>>::insert(
+				review_id,
+				Bond::<T> { owner: who.clone(), amount, unlock_at, status: BondStatus::Locked, matured },
+			);
+			<BondUnlocks<T>>::insert(unlock_at, review_id, ());
+			Ok(())
+		}
+
+		/// Give back the bond of a review whose challenge period is over. The author of a review
+		/// that stood through it earns the longevity credit once, a retracted review earns
+		/// nothing. Hidden reviews wait for moderation.
+		fn mature_bond(review_id: &T::Hash) -> DispatchResult {
+			let bond = Self::review_bond(review_id)
+				.filter(|bond| bond.status == BondStatus::Locked)
+				.ok_or(<Error<T>>::NoLockedBond)?;
+			ensure!(<frame_system::Pallet<T>>::block_number() >= bond.unlock_at, <Error<T>>::BondStillLocked);
+			ensure!(Self::review_status(review_id) == ReviewStatus::Visible, <Error<T>>::ReviewUnderModeration);
+
+			Self::release_bond(review_id);
+			if <Reviews<T>>::contains_key(review_id) {
+				if !bond.matured {
+					<ReviewBonds<T>>::mutate(review_id, |bond_option| {
+						if let Some(bond) = bond_option {
+							bond.matured = true;
+						}
+					});
+					T::Reputation::note_outcome(&bond.owner, ReviewOutcome::Matured);
+				}
+			} else {
+				// nothing is left of a retracted review once its bond is back
+				<ReviewBonds<T>>::remove(review_id);
+			}
+			Ok(())
+		}
+
+		/// Weight of giving back one bond that unlocked and crediting its author.
+		fn bond_maturation_weight() -> Weight {
+			T::DbWeight::get().reads_writes(4, 4).saturating_add(T::Reputation::outcome_weight())
+		}
+
+		/// Unreserve the bond of a review if it is still locked.
+		fn release_bond(review_id: &T::Hash) {
+			<ReviewBonds<T>>::mutate(review_id, |bond_option| {
+				if let Some(bond) = bond_option {
+					if bond.status == BondStatus::Locked {
+						T::Currency::unreserve(&bond.owner, bond.amount);
+						bond.status = BondStatus::Released;
+						Self::deposit_event(Event::BondReleased { review_id: *review_id, amount: bond.amount });
+					}
+				}
+			});
+		}
+
+		/// Settle the bond of a review removed by moderation. Within the challenge period it is
+		/// slashed: `FlaggerReward` of it is split evenly between the flaggers and the rest goes
+		/// to `Slash`. Afterwards the bond is released, without the credit of a review that
+		/// matured.
+		fn settle_bond(review_id: &T::Hash) {
+			let bond = match Self::review_bond(review_id) {
+				Some(bond) if bond.status == BondStatus::Locked => bond,
+				_ => return,
+			};
+			if <frame_system::Pallet<T>>::block_number() >= bond.unlock_at {
+				Self::release_bond(review_id);
+				return;
+			}
+
+			let (mut imbalance, _) = T::Currency::slash_reserved(&bond.owner, bond.amount);
+			let amount = imbalance.peek();
+
+			let flaggers: Vec<T::AccountId> = <ReviewFlags<T>>::iter_key_prefix(review_id).collect();
+			let mut to_flaggers: BalanceOf<T> = Zero::zero();
+			if !flaggers.is_empty() {
+				let reward = T::FlaggerReward::get().mul_floor(amount) / (flaggers.len() as u32).into();
+				for flagger in flaggers {
+					let (share, rest) = imbalance.split(reward);
+					to_flaggers = to_flaggers.saturating_add(share.peek());
+					T::Currency::resolve_creating(&flagger, share);
+					imbalance = rest;
+				}
+			}
+			T::Slash::on_unbalanced(imbalance);
+
+			<ReviewBonds<T>>::mutate(review_id, |bond_option| {
+				if let Some(bond) = bond_option {
+					bond.status = BondStatus::Slashed;
+				}
+			});
+			Self::deposit_event(Event::BondSlashed { review_id: *review_id, amount, to_flaggers });
+		}
+
+		/// Remove the revisions, votes, reply, comments and flags of a review of `owner` that is
+		/// gone. The votes it received no longer count for the reputation of `owner`. Its bond is
+		/// left to the caller.
+		fn remove_review_data(review_id: &T::Hash, owner: &T::AccountId) {
+			<ReviewWeights<T>>::remove(review_id);
+			<ReviewStatuses<T>>::remove(review_id);
+			Self::clear_flags(review_id);
+			<ReviewRevisions<T>>::remove(review_id);
+			<Replies<T>>::remove(review_id);
+			let _ = <Comments<T>>::clear_prefix(review_id, T::MaxCommentsPerReview::get(), None);
+			<NextCommentId<T>>::remove(review_id);
+			<CommentCount<T>>::remove(review_id);
+			let tally = <ReviewTallies<T>>::take(review_id);
+			if tally != VoteTally::default() {
+				let outcome = ReviewOutcome::VotesDropped { helpful: tally.helpful, unhelpful: tally.unhelpful };
+				T::Reputation::note_outcome(owner, outcome);
+			}
+			let _ = <ReviewVotes<T>>::clear_prefix(review_id, T::MaxVotesPerReview::get(), None);
+		}
+
+		/// Weight of removing a review with everything attached to it, comments, votes and flags
+		/// counted at their bounds. Flags are read twice when a slashed bond is shared. The author
+		/// loses the votes of the review and, on removal by moderation, the review itself.
+		fn review_removal_weight() -> Weight {
+			let items = T::MaxCommentsPerReview::get() as u64
+				+ T::MaxVotesPerReview::get() as u64
+				+ 2 * T::FlagThreshold::get() as u64;
+			T::DbWeight::get()
+				.reads_writes(16 + items, 28 + items)
+				.saturating_add(T::Reputation::outcome_weight().saturating_mul(2))
+		}
+
+		/// Weight of detaching `count` reviews from a removed reviewer profile.
+		fn reviewer_detach_weight(count: u64) -> Weight {
+			T::DbWeight::get().reads_writes(1 + 2 * count, 1 + 2 * count)
+		}
+
+		/// Weight of counting a star in the ratings of an app, or taking it out: whether it counts,
+		/// the release, weighted and raw aggregates with the weight of the review, the averages
+		/// and the star of the app.
+		fn rating_change_weight() -> Weight {
+			T::DbWeight::get().reads_writes(9, 6)
+		}
+
+		/// Weight of a vote: the review, its author being a maintainer, the vote and tally, then
+		/// withdrawing the previous vote and counting the new one for the author.
+		fn vote_weight() -> Weight {
+			T::DbWeight::get().reads_writes(5, 2).saturating_add(T::Reputation::outcome_weight().saturating_mul(2))
+		}
+
+		/// Up to `limit` reviews of an app ordered from most to least helpful, starting at
+		/// position `cursor`, and the position to resume from when more follow. Reviews with the
+		/// same score keep their submission order.
+		pub fn helpful_reviews_page(
+			app_hash: &T::Hash,
+			cursor: u32,
+			limit: u32,
+		) -> (Vec<(T::Hash, Review<T>, VoteTally)>, Option<u32>) {
+			let mut reviews: Vec<_> = <ReviewsOwnedByApp<T>>::get(app_hash)
+				.into_iter()
+				.filter_map(|review_id| {
+					<Reviews<T>>::get(review_id).map(|review| (review_id, review, Self::review_tally(review_id)))
+				})
+				.collect();
+			reviews.sort_by(|(_, _, a), (_, _, b)| b.score().cmp(&a.score()));
+
+			let total = reviews.len();
+			let page = reviews.into_iter().skip(cursor as usize).take(limit as usize).collect();
+			let end = cursor.saturating_add(limit);
+			let next = if (end as usize) < total { Some(end) } else { None };
+			(page, next)
+		}
+
+		/// Drop a review from the indexes of its author. Accounts could review an app more than
+		/// once before version 1, the app is only freed up when its indexed review goes.
+		fn remove_author_index(review_id: &T::Hash, review: &Review<T>) {
+			<ReviewOnwer<T>>::mutate(&review.owner, |review_vec| {
+				review_vec.retain(|id| id != review_id)
+			});
+			if Self::review_of(&review.owner, &review.app_id) == Some(*review_id) {
+				<AccountAppReviews<T>>::remove(&review.owner, &review.app_id);
+			}
+			if let Some(reviewer) = &review.reviewer {
+				<ReviewsByReviewer<T>>::remove(reviewer, review_id);
+				<ReviewerReviewCount<T>>::mutate(reviewer, |count| *count = count.saturating_sub(1));
+			}
+		}
+
+		fn bounded_content<S: Get<u32>>(content: Vec<u8>, min_len: u32) -> Result<BoundedVec<u8, S>, Error<T>> {
+			validate_text(content, min_len).map_err(|error| match error {
+				TextError::Empty => <Error<T>>::EmptyField,
+				TextError::TooShort => <Error<T>>::ContentTooShort,
+				TextError::TooLong => <Error<T>>::ContentTooLong,
+				TextError::InvalidUtf8 => <Error<T>>::InvalidUtf8,
+			})
+		}
+
+		/// Count `star` in the rating of an app and, when pinned, of its release. The weighted
+		/// rating of the app counts it with the current weight of `author`.
+		pub(crate) fn add_rating(
+			review_id: &T::Hash,
+			author: &T::AccountId,
+			app_hash: &T::Hash,
+			release: Option<SemVer>,
+			star: u32,
+		) -> DispatchResult {
+			if let Some(version) = release {
+				<ReleaseRatings<T>>::try_mutate(app_hash, version, |rating| rating.add(star))?;
+			}
+			let weight = T::RatingWeight::weight_of(author);
+			<WeightedRatings<T>>::try_mutate(app_hash, |rating| rating.add::<T>(star, weight))?;
+			<ReviewWeights<T>>::insert(review_id, weight);
+			Self::update_rating(app_hash, |rating| rating.add(star))
+		}
+
+		/// Undo `add_rating`.
+		fn remove_rating(review_id: &T::Hash, app_hash: &T::Hash, release: Option<SemVer>, star: u32) -> DispatchResult {
+			if let Some(version) = release {
+				<ReleaseRatings<T>>::try_mutate(app_hash, version, |rating| rating.remove(star))?;
+			}
+			let weight = <ReviewWeights<T>>::take(review_id);
+			<WeightedRatings<T>>::try_mutate(app_hash, |rating| rating.remove::<T>(star, weight))?;
+			Self::update_rating(app_hash, |rating| rating.remove(star))
+		}
+
+		/// Deposit for a review, which grows with the size of its content.
+		fn deposit_for(review: &Review<T>) -> BalanceOf<T> {
+			let bytes = (review.title.len() + review.cons.len() + review.pros.len()) as u32;
+			T::ReviewDepositBase::get().saturating_add(T::ReviewDepositPerByte::get().saturating_mul(bytes.into()))
+		}
+
+		/// Apply `f` to the rating aggregate of `app_hash` and push the new average to the
+		/// `RatingHandler`.
+		fn update_rating(
+			app_hash: &T::Hash,
+			f: impl FnOnce(&mut RatingAggregate<T>) -> Result<(), Error<T>>,
+		) -> DispatchResult {
+			let (average, raw) = <AppRatings<T>>::try_mutate(app_hash, |rating| -> Result<_, Error<T>> {
+				f(rating)?;
+				Ok((rating.average(), rating.precise_average()))
+			})?;
+			let weighted = Self::weighted_ratings(app_hash).average();
+			<AppRatingAverages<T>>::insert(app_hash, RatingAverages { raw, weighted });
+
+			T::RatingHandler::on_rating_changed(app_hash, average.map(|star| star.into()));
+
+			Ok(())
+		}
+
+		/// Up to `limit` reviews of an app in submission order, starting at position `cursor`,
+		/// and the position to resume from when more follow.
+		pub fn reviews_page(app_hash: &T::Hash, cursor: u32, limit: u32) -> (Vec<(T::Hash, Review<T>)>, Option<u32>) {
+			let review_ids = <ReviewsOwnedByApp<T>>::get(app_hash);
+			let reviews = review_ids
+				.iter()
+				.skip(cursor as usize)
+				.take(limit as usize)
+				.filter_map(|review_id| <Reviews<T>>::get(review_id).map(|review| (*review_id, review)))
+				.collect();
+			let end = cursor.saturating_add(limit);
+			let next = if (end as usize) < review_ids.len() { Some(end) } else { None };
+			(reviews, next)
+		}
+
+		/// Reviews written by `who`.
+		pub fn reviews_of(who: &T::AccountId) -> Vec<(T::Hash, Review<T>)> {
+			Self::review_owner(who)
+				.into_iter()
+				.filter_map(|review_id| <Reviews<T>>::get(review_id).map(|review| (review_id, review)))
+				.collect()
+		}
+
+		/// Reviews written under the reviewer profile `reviewer`.
+		pub fn reviews_of_reviewer(reviewer: &T::Hash) -> Vec<(T::Hash, Review<T>)> {
+			<ReviewsByReviewer<T>>::iter_key_prefix(reviewer)
+				.filter_map(|review_id| <Reviews<T>>::get(review_id).map(|review| (review_id, review)))
+				.collect()
+		}
+	}
+
+	impl<T: Config> OnAppDestroyed<T::Hash> for Pallet<T> {
+		fn on_app_destroyed(app_hash: &T::Hash) {
+			<AppRatings<T>>::remove(app_hash);
+			<WeightedRatings<T>>::remove(app_hash);
+			<AppRatingAverages<T>>::remove(app_hash);
+
+			if <ReviewsOwnedByApp<T>>::contains_key(app_hash) {
+				<PendingReaps<T>>::insert(app_hash, ());
+			}
+		}
+	}
+
+	impl<T: Config> OnReviewerRemoved<T::Hash> for Pallet<T> {
+		fn on_reviewer_removed(reviewer: &T::Hash) -> Weight {
+			let mut count = 0u64;
+			for review_id in <ReviewsByReviewer<T>>::drain_prefix(reviewer).map(|(review_id, _)| review_id) {
+				<Reviews<T>>::mutate(&review_id, |review| {
+					if let Some(review) = review {
+						review.reviewer = None;
+					}
+				});
+				count += 1;
+			}
+			<ReviewerReviewCount<T>>::remove(reviewer);
+
+			Self::reviewer_detach_weight(count)
+		}
+
+		fn max_weight() -> Weight {
+			Self::reviewer_detach_weight(T::MaxReviewsPerReviewer::get() as u64)
+		}
+	}
 }
@@ -0,0 +1,143 @@
+use super::*;
+use core::marker::PhantomData;
+use frame_support::pallet_prelude::*;
+use frame_support::storage::{with_transaction, TransactionOutcome};
+use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade};
+#[cfg(feature = "try-runtime")]
+use frame_support::inherent::Vec;
+
+pub mod v1 {
+    use super::*;
+
+    /// Review as stored before version 1.
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldReview<Hash, Star, Content, AccountId> {
+        pub app_id: Hash,
+        pub star: Option<Star>,
+        pub title: Content,
+        pub cons: Content,
+        pub pros: Content,
+        pub owner: AccountId,
+        pub created_date: u64,
+    }
+
+    /// Give stored reviews the `release` and `reviewer` links, and build the author indexes and
+    /// app ratings that reviews written before version 1 never filled.
+    ///
+    /// Reviews used to be unlimited per app and account. When an account reviewed an app more
+    /// than once, its newest review becomes the one indexed for that app; all of them still
+    /// count towards the rating. Retracting an older one leaves the newest indexed. Review lists
+    /// of apps could hold the same review more than once, they are deduplicated.
+    ///
+    /// Stars used to be checked against the star limit only, so a star of `0` could be stored.
+    /// Such stars, and any other that can not be rated, are dropped: the review stays without a
+    /// star and out of the rating.
+    pub struct MigrateToV1<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let onchain_version = Pallet::<T>::on_chain_storage_version();
+            if onchain_version >= 1 {
+                return T::DbWeight::get().reads(1);
+            }
+
+            let star_limit = <T as pallet_apps::Config>::StarLimit::get();
+            let mut count = 0u64;
+            Reviews::<T>::translate::<
+                OldReview<T::Hash, <T as pallet_apps::Config>::Star, BoundedVec<u8, T::ContentLimit>, T::AccountId>,
+                _,
+            >(|_, old| {
+                count += 1;
+                Some(Review::<T> {
+                    app_id: old.app_id,
+                    star: old.star.filter(|star| *star >= 1_u32.into() && *star <= star_limit.into()),
+                    title: old.title,
+                    cons: old.cons,
+                    pros: old.pros,
+                    owner: old.owner,
+                    created_date: old.created_date,
+                    release: None,
+                    reviewer: None,
+                })
+            });
+
+            let mut apps = 0u64;
+            ReviewsOwnedByApp::<T>::translate_values::<BoundedVec<T::Hash, T::ReviewOwnerByAppLimit>, _>(|review_ids| {
+                apps += 1;
+                let mut unique = BoundedVec::<T::Hash, T::ReviewOwnerByAppLimit>::default();
+                for review_id in review_ids {
+                    if !unique.contains(&review_id) {
+                        // never longer than the list it comes from
+                        let _ = unique.try_push(review_id);
+                    }
+                }
+                Some(unique)
+            });
+
+            for (review_id, review) in Reviews::<T>::iter() {
+                let _ = ReviewOnwer::<T>::try_mutate(&review.owner, |review_ids| review_ids.try_push(review_id));
+
+                let newest = match AccountAppReviews::<T>::get(&review.owner, &review.app_id).and_then(|indexed_id| Reviews::<T>::get(indexed_id)) {
+                    Some(indexed) => indexed.created_date < review.created_date,
+                    None => true,
+                };
+                if newest {
+                    AccountAppReviews::<T>::insert(&review.owner, &review.app_id, review_id);
+                }
+
+                if let Some(star) = review.star {
+                    // rating writes are all or nothing, a review they fail for is left unrated
+                    let rated = with_transaction(|| {
+                        match Pallet::<T>::add_rating(&review_id, &review.owner, &review.app_id, None, star.into()) {
+                            Ok(()) => TransactionOutcome::Commit(Ok(())),
+                            Err(error) => TransactionOutcome::Rollback(Err(error)),
+                        }
+                    });
+                    if rated.is_err() {
+                        Reviews::<T>::insert(&review_id, Review::<T> { star: None, ..review });
+                    }
+                }
+            }
+            StorageVersion::new(1).put::<Pallet<T>>();
+
+            // translating, deduplicating the review lists of apps, then per review the author
+            // indexes and the app, weighted and average ratings
+            T::DbWeight::get().reads_writes(count * 6 + apps + 1, count * 7 + apps + 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            Ok((Reviews::<T>::iter_keys().count() as u32).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            let review_count = u32::decode(&mut &state[..]).map_err(|_| "failed to decode review count")?;
+
+            ensure!(Pallet::<T>::on_chain_storage_version() == 1, "storage version not updated");
+            ensure!(Reviews::<T>::iter().count() as u32 == review_count, "reviews were lost");
+            for (review_id, review) in Reviews::<T>::iter() {
+                ensure!(
+                    AccountAppReviews::<T>::contains_key(&review.owner, &review.app_id),
+                    "review is missing from the account index"
+                );
+                ensure!(
+                    ReviewOnwer::<T>::get(&review.owner).contains(&review_id)
+                        || ReviewOnwer::<T>::get(&review.owner).is_full(),
+                    "review is missing from the author index"
+                );
+            }
+            for review_ids in ReviewsOwnedByApp::<T>::iter_values() {
+                ensure!(
+                    review_ids.iter().enumerate().all(|(i, id)| !review_ids[..i].contains(id)),
+                    "review listed twice for its app"
+                );
+            }
+            let rated = Reviews::<T>::iter_values().filter(|review| review.star.is_some()).count() as u32;
+            let counted: u32 = AppRatings::<T>::iter_values().map(|rating| rating.count).sum();
+            ensure!(counted == rated, "app ratings do not match the reviews");
+
+            Ok(())
+        }
+    }
+}
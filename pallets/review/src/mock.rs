@@ -1,11 +1,22 @@
 use crate as pallet_review;
-use frame_support::traits::{ConstU16, ConstU64};
+use frame_support::{
+	parameter_types,
+	traits::{ConstU16, ConstU32, ConstU64, Hooks},
+	weights::Weight,
+};
 use frame_system as system;
+use frame_system::EnsureRoot;
+use review_primitives::{
+	profiles::ReviewerProfiles,
+	reputation::{RatingWeight, ReputationHandler, ReviewOutcome},
+};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
+	Perbill,
 };
+use std::cell::RefCell;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -18,6 +29,9 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system,
+		Timestamp: pallet_timestamp,
+		Balances: pallet_balances,
+		AppsModule: pallet_apps,
 		ReviewModule: pallet_review,
 	}
 );
@@ -40,7 +54,7 @@ impl system::Config for Test {
 	type BlockHashCount = ConstU64<250>;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -49,11 +63,157 @@ impl system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = ConstU64<5>;
+	type WeightInfo = ();
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+impl pallet_apps::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type AppId = u32;
+	type Moment = u64;
+	type AppTime = Timestamp;
+	type Currency = Balances;
+	type AppDepositBase = ConstU64<10>;
+	type DepositPerByte = ConstU64<1>;
+	type ForceOrigin = EnsureRoot<u64>;
+	type OnAppDestroyed = ReviewModule;
+	type Star = u32;
+	type AppOwnerLimit = ConstU32<4>;
+	type StarLimit = ConstU32<5>;
+	type MaxMaintainers = ConstU32<4>;
+	type MaxReleases = ConstU32<3>;
+	type CategoryNameLimit = ConstU32<16>;
+	type TagLimit = ConstU32<8>;
+	type MaxTags = ConstU32<3>;
+	type AppNameLimit = ConstU32<16>;
+	type AppNameMinLimit = ConstU32<3>;
+	type AppSymbolLimit = ConstU32<8>;
+	type AppSymbolMinLimit = ConstU32<2>;
+}
+
 impl pallet_review::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
+	type ContentLimit = ConstU32<32>;
+	type ContentMinLimit = ConstU32<3>;
+	type ReviewOwnerLimit = ConstU32<4>;
+	type ReviewOwnerByAppLimit = ConstU32<8>;
+	type AppId = u32;
+	type Moment = u64;
+	type ReviewTime = Timestamp;
+	type RatingHandler = AppsModule;
+	type ReviewDepositBase = ConstU64<5>;
+	type ReviewDepositPerByte = ConstU64<1>;
+	type ModeratorOrigin = EnsureRoot<u64>;
+	type ReapBatchSize = ConstU32<2>;
+	type MaxRevisions = ConstU32<2>;
+	type MaxVotesPerReview = ConstU32<3>;
+	type ReplyLimit = ConstU32<32>;
+	type CommentLimit = ConstU32<32>;
+	type MaxCommentDepth = ConstU32<1>;
+	type MaxCommentsPerReview = ConstU32<4>;
+	type CommentRateWindow = ConstU64<10>;
+	type MaxCommentsPerWindow = ConstU32<3>;
+	type FlagThreshold = ConstU32<2>;
+	type ReviewBond = ConstU64<20>;
+	type ChallengePeriod = ConstU64<10>;
+	type MaxBondUnlocksPerBlock = ConstU32<2>;
+	type FlaggerReward = FlaggerReward;
+	type Slash = ();
+	type Reputation = TestReputation;
+	type RatingWeight = TestRatingWeight;
+	type Reviewers = TestReviewers;
+	type MaxReviewsPerReviewer = ConstU32<2>;
+}
+
+thread_local! {
+	static OUTCOMES: RefCell<Vec<(u64, ReviewOutcome)>> = RefCell::new(Vec::new());
+}
+
+/// Records every outcome noted for an author.
+pub struct TestReputation;
+
+impl ReputationHandler<u64> for TestReputation {
+	fn note_outcome(author: &u64, outcome: ReviewOutcome) {
+		OUTCOMES.with(|outcomes| outcomes.borrow_mut().push((*author, outcome)));
+	}
+
+	fn outcome_weight() -> Weight {
+		Weight::zero()
+	}
+}
+
+/// Outcomes noted so far, oldest first.
+pub fn outcomes() -> Vec<(u64, ReviewOutcome)> {
+	OUTCOMES.with(|outcomes| outcomes.borrow().clone())
+}
+
+/// Reviews of `BOB` weigh three times as much as the others.
+pub struct TestRatingWeight;
+
+impl RatingWeight<u64> for TestRatingWeight {
+	fn weight_of(author: &u64) -> u32 {
+		if *author == BOB { 3 } else { 1 }
+	}
+}
+
+parameter_types! {
+	pub const FlaggerReward: Perbill = Perbill::from_percent(50);
+}
+
+pub const APP_OWNER: u64 = 1;
+pub const ALICE: u64 = 2;
+pub const BOB: u64 = 3;
+pub const CAROL: u64 = 4;
+pub const ALICE_PROFILE: H256 = H256::repeat_byte(7);
+
+/// `ALICE` owns `ALICE_PROFILE`, nobody else owns a profile.
+pub struct TestReviewers;
+
+impl ReviewerProfiles<u64, H256> for TestReviewers {
+	fn is_owner(who: &u64, reviewer: &H256) -> bool {
+		*who == ALICE && *reviewer == ALICE_PROFILE
+	}
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(APP_OWNER, 1_000), (ALICE, 1_000), (BOB, 1_000), (CAROL, 1_000)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Start every block up to `n`.
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		ReviewModule::on_initialize(System::block_number());
+	}
+}
+
+/// Create an app owned by `APP_OWNER` and return its hash.
+pub fn create_app() -> H256 {
+	AppsModule::create_app(RuntimeOrigin::signed(APP_OWNER), b"Wallet".to_vec(), b"WLT".to_vec())
+		.unwrap();
+	AppsModule::app_owner(APP_OWNER)[0]
 }
@@ -1,27 +1,1025 @@
-use crate::{mock::*, Error, Event};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+	migrations::v1::{MigrateToV1, OldReview},
+	mock::*,
+	BondStatus, Error, FlagReason, ReviewStatus, Vote,
+};
+use pallet_apps::{AppRef, SemVer};
+use review_primitives::{profiles::OnReviewerRemoved, reputation::ReviewOutcome};
+use sp_core::H256;
+use frame_support::{
+	assert_noop, assert_ok,
+	pallet_prelude::{BoundedVec, ConstU32, StorageVersion, Weight},
+	storage::unhashed,
+	traits::{GetStorageVersion, Hooks, OnRuntimeUpgrade, ReservableCurrency},
+};
 
 #[test]
-fn it_works_for_default_value() {
+fn create_review_works() {
 	new_test_ext().execute_with(|| {
-		// Go past genesis block so events get deposited
-		System::set_block_number(1);
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
-		// Assert that the correct event was deposited
-		System::assert_last_event(Event::SomethingStored { something: 42, who: 1 }.into());
+		let app_hash = create_app();
+
+		assert_ok!(ReviewModule::create_review(
+			RuntimeOrigin::signed(ALICE),
+			4,
+			AppRef::Hash(app_hash),
+			b"Great".to_vec(),
+			Vec::new(),
+			b"Fast".to_vec(),
+			None,
+			None,
+		));
+
+		assert_eq!(ReviewModule::reviews_owned_by_app(app_hash).len(), 1);
+		assert_eq!(ReviewModule::app_ratings(app_hash).count, 1);
+		assert_eq!(AppsModule::apps(app_hash).unwrap().star(), Some(4));
+	});
+}
+
+#[test]
+fn create_review_rejects_empty_title() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_app();
+
+		assert_noop!(
+			ReviewModule::create_review(RuntimeOrigin::signed(ALICE), 4, AppRef::Hash(app_hash), Vec::new(), Vec::new(), Vec::new(), None, None),
+			Error::<Test>::EmptyField
+		);
+	});
+}
+
+#[test]
+fn create_review_rejects_short_title() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_app();
+
+		assert_noop!(
+			ReviewModule::create_review(RuntimeOrigin::signed(ALICE), 4, AppRef::Hash(app_hash), b"Ok".to_vec(), Vec::new(), Vec::new(), None, None),
+			Error::<Test>::ContentTooShort
+		);
+	});
+}
+
+#[test]
+fn create_review_rejects_long_content() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_app();
+
+		assert_noop!(
+			ReviewModule::create_review(RuntimeOrigin::signed(ALICE), 4, AppRef::Hash(app_hash), b"Great".to_vec(), vec![b'a'; 33], Vec::new(), None, None),
+			Error::<Test>::ContentTooLong
+		);
+	});
+}
+
+#[test]
+fn create_review_rejects_invalid_utf8() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_app();
+
+		assert_noop!(
+			ReviewModule::create_review(RuntimeOrigin::signed(ALICE), 4, AppRef::Hash(app_hash), b"Great".to_vec(), Vec::new(), vec![0xff, 0xff], None, None),
+			Error::<Test>::InvalidUtf8
+		);
+	});
+}
+
+#[test]
+fn owner_can_not_review() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_app();
+
+		assert_noop!(
+			ReviewModule::create_review(RuntimeOrigin::signed(APP_OWNER), 5, AppRef::Hash(app_hash), b"Great".to_vec(), Vec::new(), Vec::new(), None, None),
+			Error::<Test>::OwnerCanNotReview
+		);
+	});
+}
+
+#[test]
+fn reviews_page_walks_reviews_in_order() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_app();
+		assert_ok!(ReviewModule::create_review(RuntimeOrigin::signed(ALICE), 4, AppRef::Hash(app_hash), b"Great".to_vec(), Vec::new(), Vec::new(), None, None));
+		assert_ok!(ReviewModule::create_review(RuntimeOrigin::signed(BOB), 2, AppRef::Hash(app_hash), b"Slow".to_vec(), Vec::new(), Vec::new(), None, None));
+
+		let (reviews, next) = ReviewModule::reviews_page(&app_hash, 0, 1);
+		assert_eq!(reviews[0].1.owner, ALICE);
+		assert_eq!(next, Some(1));
+
+		let (reviews, next) = ReviewModule::reviews_page(&app_hash, 1, 1);
+		assert_eq!(reviews[0].1.owner, BOB);
+		assert_eq!(next, None);
+	});
+}
+
+#[test]
+fn one_review_per_app_and_account() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_app();
+		assert_ok!(ReviewModule::create_review(RuntimeOrigin::signed(ALICE), 4, AppRef::Hash(app_hash), b"Great".to_vec(), Vec::new(), Vec::new(), None, None));
+
+		let review_id = ReviewModule::reviews_owned_by_app(app_hash)[0];
+		assert_eq!(ReviewModule::review_owner(ALICE).to_vec(), vec![review_id]);
+		assert_eq!(ReviewModule::review_of(ALICE, app_hash), Some(review_id));
+
+		assert_noop!(
+			ReviewModule::create_review(RuntimeOrigin::signed(ALICE), 2, AppRef::Hash(app_hash), b"Worse".to_vec(), Vec::new(), Vec::new(), None, None),
+			Error::<Test>::AlreadyReviewed
+		);
+	});
+}
+
+#[test]
+fn removed_review_leaves_author_index() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_app();
+		assert_ok!(ReviewModule::create_review(RuntimeOrigin::signed(ALICE), 4, AppRef::Hash(app_hash), b"Great".to_vec(), Vec::new(), Vec::new(), None, None));
+		let review_id = ReviewModule::review_of(ALICE, app_hash).unwrap();
+
+		assert_ok!(ReviewModule::remove_review(RuntimeOrigin::root(), review_id));
+		assert!(ReviewModule::review_owner(ALICE).is_empty());
+		assert_eq!(ReviewModule::review_of(ALICE, app_hash), None);
+
+		assert_ok!(ReviewModule::create_review(RuntimeOrigin::signed(ALICE), 2, AppRef::Hash(app_hash), b"Worse".to_vec(), Vec::new(), Vec::new(), None, None));
+	});
+}
+
+#[test]
+fn edit_review_updates_rating_and_keeps_revisions() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_app();
+		assert_ok!(ReviewModule::create_review(RuntimeOrigin::signed(ALICE), 4, AppRef::Hash(app_hash), b"Great".to_vec(), Vec::new(), Vec::new(), None, None));
+		let review_id = ReviewModule::review_of(ALICE, app_hash).unwrap();
+
+		assert_ok!(ReviewModule::edit_review(RuntimeOrigin::signed(ALICE), review_id, 2, b"Slower".to_vec(), b"Lag".to_vec(), Vec::new()));
+		System::assert_last_event(crate::Event::<Test>::ReviewEdited { review_id, revision: 1 }.into());
+
+		let review = ReviewModule::reviews(review_id).unwrap();
+		assert_eq!(review.star, Some(2));
+		assert_eq!(review.title.to_vec(), b"Slower".to_vec());
+		assert_eq!(ReviewModule::app_ratings(app_hash).sum, 2);
+		assert_eq!(AppsModule::apps(app_hash).unwrap().star(), Some(2));
+
+		let revisions = ReviewModule::review_revisions(review_id);
+		assert_eq!(revisions.len(), 1);
+		assert_eq!(revisions[0].star, Some(4));
+	});
+}
+
+#[test]
+fn edit_review_drops_oldest_revision() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_app();
+		assert_ok!(ReviewModule::create_review(RuntimeOrigin::signed(ALICE), 4, AppRef::Hash(app_hash), b"Great".to_vec(), Vec::new(), Vec::new(), None, None));
+		let review_id = ReviewModule::review_of(ALICE, app_hash).unwrap();
+
+		for star in [3, 2, 1] {
+			assert_ok!(ReviewModule::edit_review(RuntimeOrigin::signed(ALICE), review_id, star, b"Great".to_vec(), Vec::new(), Vec::new()));
+		}
+
+		let numbers: Vec<u32> = ReviewModule::review_revisions(review_id).iter().map(|revision| revision.number).collect();
+		assert_eq!(numbers, vec![2, 3]);
+		assert_eq!(ReviewModule::app_ratings(app_hash).count, 1);
+	});
+}
+
+#[test]
+fn only_author_can_edit_review() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_app();
+		assert_ok!(ReviewModule::create_review(RuntimeOrigin::signed(ALICE), 4, AppRef::Hash(app_hash), b"Great".to_vec(), Vec::new(), Vec::new(), None, None));
+		let review_id = ReviewModule::review_of(ALICE, app_hash).unwrap();
+
+		assert_noop!(
+			ReviewModule::edit_review(RuntimeOrigin::signed(BOB), review_id, 1, b"Awful".to_vec(), Vec::new(), Vec::new()),
+			Error::<Test>::NotReviewOwner
+		);
+	});
+}
+
+#[test]
+fn retract_review_reverts_rating_and_refunds() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_app();
+		assert_ok!(ReviewModule::create_review(RuntimeOrigin::signed(ALICE), 4, AppRef::Hash(app_hash), b"Great".to_vec(), Vec::new(), Vec::new(), None, None));
+		let review_id = ReviewModule::review_of(ALICE, app_hash).unwrap();
+		assert_eq!(Balances::reserved_balance(ALICE), 5 + 5 + 20);
+
+		assert_ok!(ReviewModule::retract_review(RuntimeOrigin::signed(ALICE), review_id, false));
+
+		assert_eq!(ReviewModule::reviews(review_id), None);
+		assert!(ReviewModule::reviews_owned_by_app(app_hash).is_empty());
+		assert_eq!(ReviewModule::review_of(ALICE, app_hash), None);
+		assert_eq!(ReviewModule::app_ratings(app_hash).count, 0);
+		assert_eq!(AppsModule::apps(app_hash).unwrap().star(), None);
+		assert_eq!(ReviewModule::retracted_review(review_id), None);
+
+		// the deposit is refunded, the bond stays at stake for the challenge period
+		assert_eq!(Balances::reserved_balance(ALICE), 20);
+		assert_eq!(ReviewModule::review_bond(review_id).unwrap().status, BondStatus::Locked);
+		assert_noop!(
+			ReviewModule::claim_bond(RuntimeOrigin::signed(BOB), review_id),
+			Error::<Test>::BondStillLocked
+		);
+
+		System::set_block_number(11);
+		assert_ok!(ReviewModule::claim_bond(RuntimeOrigin::signed(BOB), review_id));
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(ReviewModule::review_bond(review_id), None);
+	});
+}
+
+#[test]
+fn retract_review_can_leave_tombstone() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_app();
+		assert_ok!(ReviewModule::create_review(RuntimeOrigin::signed(ALICE), 4, AppRef::Hash(app_hash), b"Great".to_vec(), Vec::new(), Vec::new(), None, None));
+		let review_id = ReviewModule::review_of(ALICE, app_hash).unwrap();
+
+		assert_noop!(
+			ReviewModule::retract_review(RuntimeOrigin::signed(BOB), review_id, true),
+			Error::<Test>::NotReviewOwner
+		);
+		assert_ok!(ReviewModule::retract_review(RuntimeOrigin::signed(ALICE), review_id, true));
+
+		let tombstone = ReviewModule::retracted_review(review_id).unwrap();
+		assert_eq!((tombstone.app_id, tombstone.owner), (app_hash, ALICE));
+	});
+}
+
+#[test]
+fn identical_reviews_get_distinct_ids() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_app();
+		assert_ok!(ReviewModule::create_review(RuntimeOrigin::signed(ALICE), 4, AppRef::Hash(app_hash), b"Great".to_vec(), Vec::new(), Vec::new(), None, None));
+		let first_id = ReviewModule::review_of(ALICE, app_hash).unwrap();
+		assert_ok!(ReviewModule::retract_review(RuntimeOrigin::signed(ALICE), first_id, true));
+
+		// same author, content and timestamp used to hash to the same id and overwrite the
+		// earlier entries
+		assert_ok!(ReviewModule::create_review(RuntimeOrigin::signed(ALICE), 4, AppRef::Hash(app_hash), b"Great".to_vec(), Vec::new(), Vec::new(), None, None));
+		let second_id = ReviewModule::review_of(ALICE, app_hash).unwrap();
+
+		assert_ne!(first_id, second_id);
+		assert!(ReviewModule::retracted_review(first_id).is_some());
+		assert_eq!(ReviewModule::reviews_owned_by_app(app_hash).to_vec(), vec![second_id]);
+		assert_eq!(ReviewModule::review_nonce(), 2);
+	});
+}
+
+#[test]
+fn review_id_collisions_are_rejected() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_app();
+		assert_ok!(ReviewModule::create_review(RuntimeOrigin::signed(ALICE), 4, AppRef::Hash(app_hash), b"Great".to_vec(), Vec::new(), Vec::new(), None, None));
+		let existing = ReviewModule::reviews(ReviewModule::review_of(ALICE, app_hash).unwrap()).unwrap();
+
+		// occupy the id the next review of BOB would get
+		let taken_id = ReviewModule::review_id_of(&BOB, ReviewModule::review_nonce());
+		crate::Reviews::<Test>::insert(taken_id, existing);
+
+		assert_noop!(
+			ReviewModule::create_review(RuntimeOrigin::signed(BOB), 2, AppRef::Hash(app_hash), b"Slow".to_vec(), Vec::new(), Vec::new(), None, None),
+			Error::<Test>::ReviewIdTaken
+		);
+	});
+}
+
+#[test]
+fn votes_can_be_changed() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_app();
+		assert_ok!(ReviewModule::create_review(RuntimeOrigin::signed(ALICE), 4, AppRef::Hash(app_hash), b"Great".to_vec(), Vec::new(), Vec::new(), None, None));
+		let review_id = ReviewModule::review_of(ALICE, app_hash).unwrap();
+
+		assert_ok!(ReviewModule::vote_review(RuntimeOrigin::signed(BOB), review_id, Vote::Helpful));
+		assert_noop!(
+			ReviewModule::vote_review(RuntimeOrigin::signed(BOB), review_id, Vote::Helpful),
+			Error::<Test>::AlreadyVoted
+		);
+		assert_ok!(ReviewModule::vote_review(RuntimeOrigin::signed(BOB), review_id, Vote::Unhelpful));
+
+		let tally = ReviewModule::review_tally(review_id);
+		assert_eq!((tally.helpful, tally.unhelpful), (0, 1));
+		assert_eq!(ReviewModule::review_vote(review_id, BOB), Some(Vote::Unhelpful));
+	});
+}
+
+#[test]
+fn authors_and_app_owners_can_not_vote() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_app();
+		assert_ok!(ReviewModule::create_review(RuntimeOrigin::signed(ALICE), 4, AppRef::Hash(app_hash), b"Great".to_vec(), Vec::new(), Vec::new(), None, None));
+		let review_id = ReviewModule::review_of(ALICE, app_hash).unwrap();
+
+		assert_noop!(
+			ReviewModule::vote_review(RuntimeOrigin::signed(ALICE), review_id, Vote::Helpful),
+			Error::<Test>::CanNotVoteOwnReview
+		);
+		assert_noop!(
+			ReviewModule::vote_review(RuntimeOrigin::signed(APP_OWNER), review_id, Vote::Unhelpful),
+			Error::<Test>::CanNotVoteOwnReview
+		);
+	});
+}
+
+#[test]
+fn reviews_sort_by_helpfulness() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_app();
+		assert_ok!(ReviewModule::create_review(RuntimeOrigin::signed(ALICE), 4, AppRef::Hash(app_hash), b"Great".to_vec(), Vec::new(), Vec::new(), None, None));
+		assert_ok!(ReviewModule::create_review(RuntimeOrigin::signed(BOB), 2, AppRef::Hash(app_hash), b"Slow".to_vec(), Vec::new(), Vec::new(), None, None));
+		let alice_review = ReviewModule::review_of(ALICE, app_hash).unwrap();
+		let bob_review = ReviewModule::review_of(BOB, app_hash).unwrap();
+
+		assert_ok!(ReviewModule::vote_review(RuntimeOrigin::signed(ALICE), bob_review, Vote::Helpful));
+		assert_ok!(ReviewModule::vote_review(RuntimeOrigin::signed(BOB), alice_review, Vote::Unhelpful));
+
+		let (reviews, next) = ReviewModule::helpful_reviews_page(&app_hash, 0, 2);
+		let ids: Vec<_> = reviews.iter().map(|(review_id, _, _)| *review_id).collect();
+		assert_eq!(ids, vec![bob_review, alice_review]);
+		assert_eq!(next, None);
+	});
+}
+
+#[test]
+fn app_owner_can_reply_once() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_app();
+		assert_ok!(ReviewModule::create_review(RuntimeOrigin::signed(ALICE), 2, AppRef::Hash(app_hash), b"Slow".to_vec(), Vec::new(), Vec::new(), None, None));
+		let review_id = ReviewModule::review_of(ALICE, app_hash).unwrap();
+
+		assert_noop!(
+			ReviewModule::reply_to_review(RuntimeOrigin::signed(BOB), review_id, b"Thanks".to_vec()),
+			Error::<Test>::CanNotReply
+		);
+		assert_ok!(ReviewModule::reply_to_review(RuntimeOrigin::signed(APP_OWNER), review_id, b"Fixed in 1.1".to_vec()));
+		assert_noop!(
+			ReviewModule::reply_to_review(RuntimeOrigin::signed(APP_OWNER), review_id, b"Again".to_vec()),
+			Error::<Test>::AlreadyReplied
+		);
+
+		assert_ok!(ReviewModule::edit_reply(RuntimeOrigin::signed(APP_OWNER), review_id, b"Fixed in 1.2".to_vec()));
+		let reply = ReviewModule::reply(review_id).unwrap();
+		assert_eq!(reply.content.to_vec(), b"Fixed in 1.2".to_vec());
+		assert!(reply.edited_at.is_some());
+
+		assert_ok!(ReviewModule::delete_reply(RuntimeOrigin::signed(APP_OWNER), review_id));
+		assert_eq!(ReviewModule::reply(review_id), None);
+	});
+}
+
+#[test]
+fn reply_records_rating_change() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_app();
+		assert_ok!(ReviewModule::create_review(RuntimeOrigin::signed(ALICE), 2, AppRef::Hash(app_hash), b"Slow".to_vec(), Vec::new(), Vec::new(), None, None));
+		let review_id = ReviewModule::review_of(ALICE, app_hash).unwrap();
+		assert_ok!(ReviewModule::reply_to_review(RuntimeOrigin::signed(APP_OWNER), review_id, b"Fixed in 1.1".to_vec()));
+
+		assert_ok!(ReviewModule::edit_review(RuntimeOrigin::signed(ALICE), review_id, 4, b"Fast now".to_vec(), Vec::new(), Vec::new()));
+		assert!(ReviewModule::reply(review_id).unwrap().rating_changed_after_reply);
+
+		// editing the star back does not clear the flag
+		assert_ok!(ReviewModule::edit_review(RuntimeOrigin::signed(ALICE), review_id, 2, b"Slow again".to_vec(), Vec::new(), Vec::new()));
+		assert!(ReviewModule::reply(review_id).unwrap().rating_changed_after_reply);
+	});
+}
+
+fn create_review_of_alice() -> (H256, H256) {
+	let app_hash = create_app();
+	assert_ok!(ReviewModule::create_review(RuntimeOrigin::signed(ALICE), 4, AppRef::Hash(app_hash), b"Great".to_vec(), Vec::new(), Vec::new(), None, None));
+	(app_hash, ReviewModule::review_of(ALICE, app_hash).unwrap())
+}
+
+#[test]
+fn comments_thread_is_bounded_in_depth() {
+	new_test_ext().execute_with(|| {
+		let (_, review_id) = create_review_of_alice();
+
+		assert_ok!(ReviewModule::post_comment(RuntimeOrigin::signed(BOB), review_id, None, b"Agreed".to_vec()));
+		assert_ok!(ReviewModule::post_comment(RuntimeOrigin::signed(ALICE), review_id, Some(0), b"Thanks".to_vec()));
+		assert_noop!(
+			ReviewModule::post_comment(RuntimeOrigin::signed(BOB), review_id, Some(1), b"Welcome".to_vec()),
+			Error::<Test>::CommentTooDeep
+		);
+		assert_noop!(
+			ReviewModule::post_comment(RuntimeOrigin::signed(BOB), review_id, Some(7), b"Hello".to_vec()),
+			Error::<Test>::CommentNotExist
+		);
+
+		let comment = ReviewModule::comment(review_id, 1).unwrap();
+		assert_eq!((comment.author, comment.parent, comment.depth), (ALICE, Some(0), 1));
+	});
+}
+
+#[test]
+fn comments_are_paginated_and_counted() {
+	new_test_ext().execute_with(|| {
+		let (_, review_id) = create_review_of_alice();
+		for _ in 0..3 {
+			assert_ok!(ReviewModule::post_comment(RuntimeOrigin::signed(BOB), review_id, None, b"Agreed".to_vec()));
+		}
+		assert_ok!(ReviewModule::delete_comment(RuntimeOrigin::signed(BOB), review_id, 1));
+		assert_eq!(ReviewModule::comment_count(review_id), 2);
+
+		let (comments, next) = ReviewModule::comments_page(&review_id, 0, 2);
+		assert_eq!(comments.iter().map(|(id, _)| *id).collect::<Vec<_>>(), vec![0, 2]);
+		assert_eq!(next, None);
+	});
+}
+
+#[test]
+fn comments_are_rate_limited() {
+	new_test_ext().execute_with(|| {
+		let (_, review_id) = create_review_of_alice();
+		for _ in 0..3 {
+			assert_ok!(ReviewModule::post_comment(RuntimeOrigin::signed(BOB), review_id, None, b"Agreed".to_vec()));
+		}
+		assert_noop!(
+			ReviewModule::post_comment(RuntimeOrigin::signed(BOB), review_id, None, b"Agreed".to_vec()),
+			Error::<Test>::CommentRateLimited
+		);
+
+		System::set_block_number(11);
+		assert_ok!(ReviewModule::post_comment(RuntimeOrigin::signed(BOB), review_id, None, b"Agreed".to_vec()));
+		assert_noop!(
+			ReviewModule::post_comment(RuntimeOrigin::signed(ALICE), review_id, None, b"Enough".to_vec()),
+			Error::<Test>::TooManyComments
+		);
+	});
+}
+
+#[test]
+fn comments_can_be_edited_and_moderated() {
+	new_test_ext().execute_with(|| {
+		let (_, review_id) = create_review_of_alice();
+		assert_ok!(ReviewModule::post_comment(RuntimeOrigin::signed(BOB), review_id, None, b"Agreed".to_vec()));
+
+		assert_noop!(
+			ReviewModule::edit_comment(RuntimeOrigin::signed(ALICE), review_id, 0, b"Changed".to_vec()),
+			Error::<Test>::NotCommentAuthor
+		);
+		assert_ok!(ReviewModule::edit_comment(RuntimeOrigin::signed(BOB), review_id, 0, b"Changed".to_vec()));
+		assert!(ReviewModule::comment(review_id, 0).unwrap().edited_at.is_some());
+
+		assert_ok!(ReviewModule::moderate_comment(RuntimeOrigin::root(), review_id, 0));
+		assert_eq!(ReviewModule::comment(review_id, 0), None);
+	});
+}
+
+#[test]
+fn flagged_review_is_hidden_from_ratings() {
+	new_test_ext().execute_with(|| {
+		let (app_hash, review_id) = create_review_of_alice();
+
+		assert_noop!(
+			ReviewModule::flag_review(RuntimeOrigin::signed(ALICE), review_id, FlagReason::Spam),
+			Error::<Test>::CanNotFlagOwnReview
+		);
+		assert_noop!(
+			ReviewModule::flag_review(RuntimeOrigin::signed(APP_OWNER), review_id, FlagReason::Spam),
+			Error::<Test>::CanNotFlagOwnReview
+		);
+		assert_ok!(ReviewModule::flag_review(RuntimeOrigin::signed(BOB), review_id, FlagReason::Spam));
+		assert_noop!(
+			ReviewModule::flag_review(RuntimeOrigin::signed(BOB), review_id, FlagReason::Abuse),
+			Error::<Test>::AlreadyFlagged
+		);
+		assert_eq!(ReviewModule::review_status(review_id), ReviewStatus::Visible);
+
+		assert_ok!(ReviewModule::flag_review(RuntimeOrigin::signed(CAROL), review_id, FlagReason::Fake));
+		assert_eq!(ReviewModule::review_status(review_id), ReviewStatus::Hidden);
+		assert_eq!(ReviewModule::app_ratings(app_hash).count, 0);
+		// flags stop once the review waits for a moderator
+		assert_noop!(
+			ReviewModule::flag_review(RuntimeOrigin::signed(5), review_id, FlagReason::Spam),
+			Error::<Test>::ReviewUnderModeration
+		);
+		assert_eq!(AppsModule::apps(app_hash).unwrap().star(), None);
+		// still there for appeals
+		assert!(ReviewModule::reviews(review_id).is_some());
+	});
+}
+
+#[test]
+fn moderator_can_restore_hidden_review() {
+	new_test_ext().execute_with(|| {
+		let (app_hash, review_id) = create_review_of_alice();
+		assert_noop!(
+			ReviewModule::restore_review(RuntimeOrigin::root(), review_id),
+			Error::<Test>::ReviewNotHidden
+		);
+		assert_ok!(ReviewModule::flag_review(RuntimeOrigin::signed(BOB), review_id, FlagReason::Spam));
+		assert_ok!(ReviewModule::flag_review(RuntimeOrigin::signed(CAROL), review_id, FlagReason::Spam));
+
+		assert_ok!(ReviewModule::restore_review(RuntimeOrigin::root(), review_id));
+		assert_eq!(ReviewModule::review_status(review_id), ReviewStatus::Visible);
+		assert_eq!(ReviewModule::flag_count(review_id), 0);
+		assert_eq!(ReviewModule::app_ratings(app_hash).count, 1);
+	});
+}
+
+#[test]
+fn removing_hidden_review_keeps_ratings_consistent() {
+	new_test_ext().execute_with(|| {
+		let (app_hash, review_id) = create_review_of_alice();
+		assert_ok!(ReviewModule::flag_review(RuntimeOrigin::signed(BOB), review_id, FlagReason::Abuse));
+		assert_ok!(ReviewModule::flag_review(RuntimeOrigin::signed(CAROL), review_id, FlagReason::Abuse));
+
+		assert_ok!(ReviewModule::remove_review(RuntimeOrigin::root(), review_id));
+		assert_eq!(ReviewModule::app_ratings(app_hash).count, 0);
+		assert_eq!(ReviewModule::review_status(review_id), ReviewStatus::Visible);
+	});
+}
+
+#[test]
+fn flagged_review_can_not_be_retracted() {
+	new_test_ext().execute_with(|| {
+		let (_, review_id) = create_review_of_alice();
+		assert_ok!(ReviewModule::flag_review(RuntimeOrigin::signed(BOB), review_id, FlagReason::Fake));
+		assert_noop!(
+			ReviewModule::retract_review(RuntimeOrigin::signed(ALICE), review_id, false),
+			Error::<Test>::ReviewUnderModeration
+		);
+
+		// once a moderator dismisses the flag the author is free to go
+		assert_ok!(ReviewModule::restore_review(RuntimeOrigin::root(), review_id));
+		assert_eq!(ReviewModule::flag_count(review_id), 0);
+		assert_ok!(ReviewModule::retract_review(RuntimeOrigin::signed(ALICE), review_id, false));
+	});
+}
+
+#[test]
+fn bond_can_be_claimed_after_challenge_period() {
+	new_test_ext().execute_with(|| {
+		let (_, review_id) = create_review_of_alice();
+		assert_eq!(ReviewModule::review_bond(review_id).unwrap().unlock_at, 11);
+
+		assert_noop!(
+			ReviewModule::claim_bond(RuntimeOrigin::signed(BOB), review_id),
+			Error::<Test>::BondStillLocked
+		);
+
+		System::set_block_number(11);
+		assert_ok!(ReviewModule::claim_bond(RuntimeOrigin::signed(BOB), review_id));
+		assert_eq!(ReviewModule::review_bond(review_id).unwrap().status, BondStatus::Released);
+		assert_eq!(Balances::reserved_balance(ALICE), 5 + 5);
+		assert_noop!(
+			ReviewModule::claim_bond(RuntimeOrigin::signed(BOB), review_id),
+			Error::<Test>::NoLockedBond
+		);
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn removal_within_challenge_period_slashes_bond() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
+		let (_, review_id) = create_review_of_alice();
+		assert_ok!(ReviewModule::flag_review(RuntimeOrigin::signed(BOB), review_id, FlagReason::Abuse));
+		assert_ok!(ReviewModule::flag_review(RuntimeOrigin::signed(CAROL), review_id, FlagReason::Abuse));
 		assert_noop!(
-			TemplateModule::cause_error(RuntimeOrigin::signed(1)),
-			Error::<Test>::NoneValue
+			ReviewModule::retract_review(RuntimeOrigin::signed(ALICE), review_id, false),
+			Error::<Test>::ReviewUnderModeration
 		);
+
+		assert_ok!(ReviewModule::remove_review(RuntimeOrigin::root(), review_id));
+
+		// half of the bond is shared by the two flaggers, the rest is burnt
+		assert_eq!(Balances::free_balance(BOB), 1_000 + 5);
+		assert_eq!(Balances::free_balance(CAROL), 1_000 + 5);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::free_balance(ALICE), 1_000 - 5 - 5 - 20);
+		assert_eq!(ReviewModule::review_bond(review_id).unwrap().status, BondStatus::Slashed);
+	});
+}
+
+#[test]
+fn removal_after_challenge_period_releases_bond() {
+	new_test_ext().execute_with(|| {
+		let (_, review_id) = create_review_of_alice();
+		System::set_block_number(11);
+
+		assert_ok!(ReviewModule::remove_review(RuntimeOrigin::root(), review_id));
+
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::free_balance(ALICE), 1_000 - 5 - 5);
+		// removal never earns the longevity credit
+		assert_eq!(outcomes(), vec![(ALICE, ReviewOutcome::Removed)]);
+	});
+}
+
+#[test]
+fn bond_is_released_when_challenge_period_ends() {
+	new_test_ext().execute_with(|| {
+		let (_, review_id) = create_review_of_alice();
+
+		run_to_block(10);
+		assert_eq!(ReviewModule::review_bond(review_id).unwrap().status, BondStatus::Locked);
+		assert!(outcomes().is_empty());
+
+		run_to_block(11);
+		assert_eq!(ReviewModule::review_bond(review_id).unwrap().status, BondStatus::Released);
+		assert_eq!(Balances::reserved_balance(ALICE), 5 + 5);
+		assert_eq!(outcomes(), vec![(ALICE, ReviewOutcome::Matured)]);
+
+		// the review is gone, the credit is not taken back
+		assert_ok!(ReviewModule::remove_review(RuntimeOrigin::root(), review_id));
+		assert_eq!(outcomes(), vec![(ALICE, ReviewOutcome::Matured), (ALICE, ReviewOutcome::Removed)]);
+	});
+}
+
+#[test]
+fn bonds_past_the_block_limit_wait_for_the_next_block() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_app();
+		for author in [ALICE, BOB, CAROL] {
+			assert_ok!(ReviewModule::create_review(RuntimeOrigin::signed(author), 4, AppRef::Hash(app_hash), b"Great".to_vec(), Vec::new(), Vec::new(), None, None));
+		}
+		let released = || {
+			[ALICE, BOB, CAROL]
+				.into_iter()
+				.filter(|author| {
+					let review_id = ReviewModule::review_of(author, app_hash).unwrap();
+					ReviewModule::review_bond(review_id).unwrap().status == BondStatus::Released
+				})
+				.count()
+		};
+
+		// two bonds are given back per block
+		run_to_block(11);
+		assert_eq!(released(), 2);
+		assert_eq!(ReviewModule::unlock_cursor(), Some(11));
+
+		run_to_block(12);
+		assert_eq!(released(), 3);
+		assert_eq!(outcomes().len(), 3);
+		assert_eq!(ReviewModule::unlock_cursor(), Some(13));
+	});
+}
+
+#[test]
+fn hidden_review_matures_once_restored() {
+	new_test_ext().execute_with(|| {
+		let (_, review_id) = create_review_of_alice();
+		assert_ok!(ReviewModule::flag_review(RuntimeOrigin::signed(BOB), review_id, FlagReason::Abuse));
+		assert_ok!(ReviewModule::flag_review(RuntimeOrigin::signed(CAROL), review_id, FlagReason::Abuse));
+
+		run_to_block(11);
+		assert_eq!(ReviewModule::review_bond(review_id).unwrap().status, BondStatus::Locked);
+		assert!(outcomes().is_empty());
+
+		assert_ok!(ReviewModule::restore_review(RuntimeOrigin::root(), review_id));
+		assert_eq!(ReviewModule::review_bond(review_id).unwrap().status, BondStatus::Released);
+		assert_eq!(outcomes(), vec![(ALICE, ReviewOutcome::Matured)]);
+	});
+}
+
+#[test]
+fn votes_of_a_review_that_is_gone_stop_counting() {
+	new_test_ext().execute_with(|| {
+		let (app_hash, review_id) = create_review_of_alice();
+		assert_ok!(ReviewModule::vote_review(RuntimeOrigin::signed(BOB), review_id, Vote::Helpful));
+		assert_ok!(ReviewModule::vote_review(RuntimeOrigin::signed(CAROL), review_id, Vote::Unhelpful));
+
+		assert_ok!(ReviewModule::retract_review(RuntimeOrigin::signed(ALICE), review_id, false));
+		assert_eq!(outcomes().last(), Some(&(ALICE, ReviewOutcome::VotesDropped { helpful: 1, unhelpful: 1 })));
+
+		// a review without votes has nothing to take back
+		assert_ok!(ReviewModule::create_review(RuntimeOrigin::signed(ALICE), 4, AppRef::Hash(app_hash), b"Again".to_vec(), Vec::new(), Vec::new(), None, None));
+		let review_id = ReviewModule::review_of(ALICE, app_hash).unwrap();
+		let noted = outcomes().len();
+		assert_ok!(ReviewModule::remove_review(RuntimeOrigin::root(), review_id));
+		assert_eq!(outcomes()[noted..].to_vec(), vec![(ALICE, ReviewOutcome::Removed)]);
+	});
+}
+
+#[test]
+fn review_matures_once() {
+	new_test_ext().execute_with(|| {
+		let (_, review_id) = create_review_of_alice();
+		run_to_block(11);
+		assert_eq!(outcomes(), vec![(ALICE, ReviewOutcome::Matured)]);
+
+		// editing a review whose bond was given back bonds it again, without a second credit
+		assert_ok!(ReviewModule::edit_review(RuntimeOrigin::signed(ALICE), review_id, 3, b"Fine".to_vec(), Vec::new(), Vec::new()));
+		assert_eq!(Balances::reserved_balance(ALICE), 5 + 4 + 20);
+		run_to_block(21);
+		assert_eq!(ReviewModule::review_bond(review_id).unwrap().status, BondStatus::Released);
+		assert_eq!(Balances::reserved_balance(ALICE), 5 + 4);
+		assert_eq!(outcomes(), vec![(ALICE, ReviewOutcome::Matured)]);
+	});
+}
+
+#[test]
+fn edited_review_matures_at_its_new_unlock() {
+	new_test_ext().execute_with(|| {
+		let (_, review_id) = create_review_of_alice();
+		run_to_block(5);
+		assert_ok!(ReviewModule::edit_review(RuntimeOrigin::signed(ALICE), review_id, 3, b"Fine".to_vec(), Vec::new(), Vec::new()));
+		assert_eq!(ReviewModule::review_bond(review_id).unwrap().unlock_at, 15);
+
+		run_to_block(14);
+		assert_eq!(ReviewModule::review_bond(review_id).unwrap().status, BondStatus::Locked);
+		run_to_block(15);
+		assert_eq!(ReviewModule::review_bond(review_id).unwrap().status, BondStatus::Released);
+		assert_eq!(outcomes(), vec![(ALICE, ReviewOutcome::Matured)]);
+	});
+}
+
+#[test]
+fn ratings_keep_raw_and_weighted_averages() {
+	new_test_ext().execute_with(|| {
+		let (app_hash, _) = create_review_of_alice();
+		assert_ok!(ReviewModule::create_review(RuntimeOrigin::signed(BOB), 1, AppRef::Hash(app_hash), b"Awful".to_vec(), Vec::new(), Vec::new(), None, None));
+
+		// BOB weighs three times as much as ALICE
+		let averages = ReviewModule::rating_averages(app_hash);
+		assert_eq!(averages.raw, Some(250));
+		assert_eq!(averages.weighted, Some(175));
+
+		let review_id = ReviewModule::review_of(BOB, app_hash).unwrap();
+		assert_ok!(ReviewModule::retract_review(RuntimeOrigin::signed(BOB), review_id, false));
+		let averages = ReviewModule::rating_averages(app_hash);
+		assert_eq!(averages.raw, Some(400));
+		assert_eq!(averages.weighted, Some(400));
+		assert_eq!(ReviewModule::rating_weight(review_id), 0);
+	});
+}
+
+#[test]
+fn reviews_can_be_written_under_own_profile() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_app();
+		assert_noop!(
+			ReviewModule::create_review(RuntimeOrigin::signed(BOB), 2, AppRef::Hash(app_hash), b"Slow".to_vec(), Vec::new(), Vec::new(), None, Some(ALICE_PROFILE)),
+			Error::<Test>::NotReviewerOwner
+		);
+
+		assert_ok!(ReviewModule::create_review(RuntimeOrigin::signed(ALICE), 4, AppRef::Hash(app_hash), b"Great".to_vec(), Vec::new(), Vec::new(), None, Some(ALICE_PROFILE)));
+		let review_id = ReviewModule::review_of(ALICE, app_hash).unwrap();
+		assert_eq!(ReviewModule::reviews(review_id).unwrap().reviewer, Some(ALICE_PROFILE));
+		let history: Vec<_> = ReviewModule::reviews_of_reviewer(&ALICE_PROFILE).into_iter().map(|(id, _)| id).collect();
+		assert_eq!(history, vec![review_id]);
+
+		assert_ok!(ReviewModule::retract_review(RuntimeOrigin::signed(ALICE), review_id, false));
+		assert!(ReviewModule::reviews_of_reviewer(&ALICE_PROFILE).is_empty());
+		assert_eq!(ReviewModule::reviewer_review(ALICE_PROFILE, review_id), None);
+	});
+}
+
+#[test]
+fn removed_reviewer_is_detached_from_its_reviews() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_app();
+		assert_ok!(ReviewModule::create_review(RuntimeOrigin::signed(ALICE), 4, AppRef::Hash(app_hash), b"Great".to_vec(), Vec::new(), Vec::new(), None, Some(ALICE_PROFILE)));
+		let review_id = ReviewModule::review_of(ALICE, app_hash).unwrap();
+
+		<ReviewModule as OnReviewerRemoved<H256>>::on_reviewer_removed(&ALICE_PROFILE);
+
+		assert!(ReviewModule::reviews_of_reviewer(&ALICE_PROFILE).is_empty());
+		assert_eq!(ReviewModule::reviewer_review(ALICE_PROFILE, review_id), None);
+		// the review itself stays, written by its account only
+		assert_eq!(ReviewModule::reviews(review_id).unwrap().reviewer, None);
+		assert_eq!(ReviewModule::reviews(review_id).unwrap().owner, ALICE);
+		assert_eq!(ReviewModule::reviewer_review_count(ALICE_PROFILE), 0);
+	});
+}
+
+#[test]
+fn reviews_per_reviewer_are_bounded() {
+	new_test_ext().execute_with(|| {
+		let mut apps = vec![create_app()];
+		for (name, symbol) in [(b"Chat", b"CHT"), (b"Maps", b"MAP")] {
+			assert_ok!(AppsModule::create_app(RuntimeOrigin::signed(APP_OWNER), name.to_vec(), symbol.to_vec()));
+			apps.push(AppsModule::app_owner(APP_OWNER)[apps.len()]);
+		}
+		let review_under_profile = |app_hash: H256| {
+			ReviewModule::create_review(RuntimeOrigin::signed(ALICE), 4, AppRef::Hash(app_hash), b"Great".to_vec(), Vec::new(), Vec::new(), None, Some(ALICE_PROFILE))
+		};
+
+		assert_ok!(review_under_profile(apps[0]));
+		assert_ok!(review_under_profile(apps[1]));
+		assert_noop!(review_under_profile(apps[2]), Error::<Test>::TooManyReviewerReviews);
+
+		// retracting a review frees its place under the profile
+		let review_id = ReviewModule::review_of(ALICE, apps[0]).unwrap();
+		assert_ok!(ReviewModule::retract_review(RuntimeOrigin::signed(ALICE), review_id, false));
+		assert_eq!(ReviewModule::reviewer_review_count(ALICE_PROFILE), 1);
+		assert_ok!(review_under_profile(apps[2]));
+	});
+}
+
+#[test]
+fn destroyed_app_reviews_are_reaped_in_batches() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_app();
+		for author in [ALICE, BOB, CAROL] {
+			assert_ok!(ReviewModule::create_review(RuntimeOrigin::signed(author), 4, AppRef::Hash(app_hash), b"Great".to_vec(), Vec::new(), Vec::new(), None, None));
+		}
+		assert_noop!(ReviewModule::reap_reviews(RuntimeOrigin::signed(BOB), app_hash), Error::<Test>::NothingToReap);
+
+		assert_ok!(AppsModule::destroy_app(RuntimeOrigin::signed(APP_OWNER), AppRef::Hash(app_hash)));
+		assert_eq!(ReviewModule::app_ratings(app_hash).count, 0);
+		assert!(ReviewModule::pending_reaps(app_hash).is_some());
+
+		// one call removes at most `ReapBatchSize` reviews
+		assert_ok!(ReviewModule::reap_reviews(RuntimeOrigin::signed(BOB), app_hash));
+		assert_eq!(ReviewModule::reviews_owned_by_app(app_hash).len(), 1);
+		assert_eq!(Balances::reserved_balance(CAROL), 0);
+		assert_eq!(ReviewModule::review_of(CAROL, app_hash), None);
+
+		// idle blocks without spare weight do nothing
+		assert_eq!(ReviewModule::on_idle(1, Weight::zero()), Weight::zero());
+		assert_eq!(ReviewModule::reviews_owned_by_app(app_hash).len(), 1);
+
+		ReviewModule::on_idle(1, Weight::MAX);
+		assert!(ReviewModule::reviews_owned_by_app(app_hash).is_empty());
+		assert!(ReviewModule::pending_reaps(app_hash).is_none());
+		for author in [ALICE, BOB] {
+			assert_eq!(Balances::reserved_balance(author), 0);
+			assert!(ReviewModule::review_owner(author).is_empty());
+		}
+		assert_noop!(ReviewModule::reap_reviews(RuntimeOrigin::signed(BOB), app_hash), Error::<Test>::NothingToReap);
+	});
+}
+
+#[test]
+fn pinned_reviews_are_rated_per_release() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_app();
+		let v1 = SemVer { major: 1, minor: 0, patch: 0 };
+		let v2 = SemVer { major: 2, minor: 0, patch: 0 };
+
+		assert_noop!(
+			ReviewModule::create_review(RuntimeOrigin::signed(ALICE), 4, AppRef::Hash(app_hash), b"Great".to_vec(), Vec::new(), Vec::new(), Some(v1), None),
+			Error::<Test>::ReleaseNotExist
+		);
+		for version in [v1, v2] {
+			assert_ok!(AppsModule::publish_release(RuntimeOrigin::signed(APP_OWNER), AppRef::Hash(app_hash), version, H256::repeat_byte(1), H256::repeat_byte(2)));
+		}
+
+		assert_ok!(ReviewModule::create_review(RuntimeOrigin::signed(ALICE), 4, AppRef::Hash(app_hash), b"Great".to_vec(), Vec::new(), Vec::new(), Some(v1), None));
+		assert_ok!(ReviewModule::create_review(RuntimeOrigin::signed(BOB), 2, AppRef::Hash(app_hash), b"Slow".to_vec(), Vec::new(), Vec::new(), Some(v2), None));
+		assert_ok!(ReviewModule::create_review(RuntimeOrigin::signed(CAROL), 3, AppRef::Hash(app_hash), b"Fine".to_vec(), Vec::new(), Vec::new(), None, None));
+
+		assert_eq!(ReviewModule::app_ratings(app_hash).count, 3);
+		assert_eq!((ReviewModule::release_ratings(app_hash, v1).count, ReviewModule::release_ratings(app_hash, v1).sum), (1, 4));
+		assert_eq!((ReviewModule::release_ratings(app_hash, v2).count, ReviewModule::release_ratings(app_hash, v2).sum), (1, 2));
+
+		let review_id = ReviewModule::review_of(BOB, app_hash).unwrap();
+		assert_ok!(ReviewModule::edit_review(RuntimeOrigin::signed(BOB), review_id, 5, b"Fast now".to_vec(), Vec::new(), Vec::new()));
+		assert_eq!(ReviewModule::release_ratings(app_hash, v2).sum, 5);
+
+		assert_ok!(ReviewModule::retract_review(RuntimeOrigin::signed(BOB), review_id, false));
+		assert_eq!(ReviewModule::release_ratings(app_hash, v2).count, 0);
+		assert_eq!(ReviewModule::release_ratings(app_hash, v1).count, 1);
+	});
+}
+
+#[test]
+fn migration_indexes_and_rates_old_reviews() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_app();
+		let old_review = |star: u32, owner: u64, created_date: u64| OldReview {
+			app_id: app_hash,
+			star: Some(star),
+			title: BoundedVec::<u8, ConstU32<32>>::truncate_from(b"Great".to_vec()),
+			cons: BoundedVec::<u8, ConstU32<32>>::default(),
+			pros: BoundedVec::<u8, ConstU32<32>>::default(),
+			owner,
+			created_date,
+		};
+		let first = H256::repeat_byte(1);
+		let second = H256::repeat_byte(2);
+		let third = H256::repeat_byte(3);
+		unhashed::put(&crate::Reviews::<Test>::hashed_key_for(first), &old_review(4, ALICE, 1));
+		unhashed::put(&crate::Reviews::<Test>::hashed_key_for(second), &old_review(2, BOB, 1));
+		// a second review of the same app by the same account
+		unhashed::put(&crate::Reviews::<Test>::hashed_key_for(third), &old_review(3, ALICE, 2));
+		// reviews could be listed twice for their app
+		crate::ReviewsOwnedByApp::<Test>::insert(app_hash, BoundedVec::truncate_from(vec![first, second, first, third]));
+		StorageVersion::new(0).put::<ReviewModule>();
+
+		MigrateToV1::<Test>::on_runtime_upgrade();
+
+		let review = ReviewModule::reviews(first).unwrap();
+		assert_eq!((review.star, review.owner, review.release, review.reviewer), (Some(4), ALICE, None, None));
+		assert_eq!(ReviewModule::review_of(ALICE, app_hash), Some(third));
+		assert_eq!(ReviewModule::review_of(BOB, app_hash), Some(second));
+		assert_eq!(ReviewModule::review_owner(ALICE).len(), 2);
+		assert_eq!(ReviewModule::review_owner(BOB).to_vec(), vec![second]);
+
+		let rating = ReviewModule::app_ratings(app_hash);
+		assert_eq!((rating.count, rating.sum), (3, 9));
+		assert_eq!(AppsModule::apps(app_hash).unwrap().star(), Some(3));
+		assert_eq!(ReviewModule::reviews_owned_by_app(app_hash).to_vec(), vec![first, second, third]);
+		assert_eq!(ReviewModule::on_chain_storage_version(), 1);
+
+		// retracting the older review keeps the newest one indexed
+		assert_ok!(ReviewModule::retract_review(RuntimeOrigin::signed(ALICE), first, false));
+		assert_eq!(ReviewModule::review_of(ALICE, app_hash), Some(third));
+		assert_noop!(
+			ReviewModule::create_review(RuntimeOrigin::signed(ALICE), 5, AppRef::Hash(app_hash), b"Again".to_vec(), Vec::new(), Vec::new(), None, None),
+			Error::<Test>::AlreadyReviewed
+		);
+		assert_eq!(ReviewModule::reviews_owned_by_app(app_hash).to_vec(), vec![second, third]);
+	});
+}
+
+#[test]
+fn migration_drops_stars_out_of_range() {
+	new_test_ext().execute_with(|| {
+		let app_hash = create_app();
+		let old_review = |star: u32, owner: u64| OldReview {
+			app_id: app_hash,
+			star: Some(star),
+			title: BoundedVec::<u8, ConstU32<32>>::truncate_from(b"Great".to_vec()),
+			cons: BoundedVec::<u8, ConstU32<32>>::default(),
+			pros: BoundedVec::<u8, ConstU32<32>>::default(),
+			owner,
+			created_date: 1,
+		};
+		let zero = H256::repeat_byte(1);
+		let too_high = H256::repeat_byte(2);
+		let valid = H256::repeat_byte(3);
+		unhashed::put(&crate::Reviews::<Test>::hashed_key_for(zero), &old_review(0, ALICE));
+		unhashed::put(&crate::Reviews::<Test>::hashed_key_for(too_high), &old_review(6, BOB));
+		unhashed::put(&crate::Reviews::<Test>::hashed_key_for(valid), &old_review(4, CAROL));
+		StorageVersion::new(0).put::<ReviewModule>();
+
+		MigrateToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(ReviewModule::reviews(zero).unwrap().star, None);
+		assert_eq!(ReviewModule::reviews(too_high).unwrap().star, None);
+		assert_eq!(ReviewModule::reviews(valid).unwrap().star, Some(4));
+		let rating = ReviewModule::app_ratings(app_hash);
+		assert_eq!((rating.count, rating.sum), (1, 4));
+		assert_eq!(ReviewModule::weighted_ratings(app_hash).weight, 1);
+
+		// unrated reviews can still be retracted
+		assert_ok!(ReviewModule::retract_review(RuntimeOrigin::signed(ALICE), zero, false));
+		assert_ok!(ReviewModule::retract_review(RuntimeOrigin::signed(BOB), too_high, false));
+		assert_eq!(ReviewModule::app_ratings(app_hash).count, 1);
+	});
+}
+
+#[test]
+fn removed_review_deposit_is_slashed() {
+	new_test_ext().execute_with(|| {
+		let (_, review_id) = create_review_of_alice();
+		let deposit = ReviewModule::review_deposit(review_id);
+		let issuance = Balances::total_issuance();
+
+		assert_noop!(ReviewModule::remove_review(RuntimeOrigin::signed(BOB), review_id), sp_runtime::DispatchError::BadOrigin);
+
+		System::set_block_number(11);
+		assert_ok!(ReviewModule::remove_review(RuntimeOrigin::root(), review_id));
+		System::assert_last_event(crate::Event::<Test>::ReviewRemoved { review_id, slashed: deposit }.into());
+		assert_eq!(ReviewModule::review_deposit(review_id), 0);
+		assert_eq!(Balances::total_issuance(), issuance - deposit);
+		assert_eq!(Balances::free_balance(ALICE), 1_000 - deposit);
+	});
+}
+
+#[test]
+fn votes_per_review_are_bounded() {
+	new_test_ext().execute_with(|| {
+		let (_, review_id) = create_review_of_alice();
+		for voter in [BOB, CAROL, 5] {
+			assert_ok!(ReviewModule::vote_review(RuntimeOrigin::signed(voter), review_id, Vote::Helpful));
+		}
+		assert_noop!(
+			ReviewModule::vote_review(RuntimeOrigin::signed(6), review_id, Vote::Helpful),
+			Error::<Test>::TooManyVotes
+		);
+
+		// voters already counted can still change their vote
+		assert_ok!(ReviewModule::vote_review(RuntimeOrigin::signed(BOB), review_id, Vote::Unhelpful));
+		let tally = ReviewModule::review_tally(review_id);
+		assert_eq!((tally.helpful, tally.unhelpful), (2, 1));
+	});
+}
+
+#[test]
+fn edited_review_is_bonded_again() {
+	new_test_ext().execute_with(|| {
+		let (_, review_id) = create_review_of_alice();
+
+		// editing a review restarts its challenge period
+		System::set_block_number(5);
+		assert_ok!(ReviewModule::edit_review(RuntimeOrigin::signed(ALICE), review_id, 3, b"Good".to_vec(), Vec::new(), Vec::new()));
+		assert_eq!(ReviewModule::review_bond(review_id).unwrap().unlock_at, 15);
+		assert_eq!(Balances::reserved_balance(ALICE), 5 + 4 + 20);
+
+		System::set_block_number(15);
+		assert_ok!(ReviewModule::claim_bond(RuntimeOrigin::signed(BOB), review_id));
+		assert_eq!(Balances::reserved_balance(ALICE), 5 + 4);
+
+		// once given back, the bond is reserved again
+		assert_ok!(ReviewModule::edit_review(RuntimeOrigin::signed(ALICE), review_id, 2, b"Okay".to_vec(), Vec::new(), Vec::new()));
+		let bond = ReviewModule::review_bond(review_id).unwrap();
+		assert_eq!((bond.status, bond.unlock_at), (BondStatus::Locked, 25));
+		assert_eq!(Balances::reserved_balance(ALICE), 5 + 4 + 20);
 	});
 }
@@ -5,15 +5,17 @@
 /// <https://docs.substrate.io/reference/frame-pallets/>
 pub use pallet::*;
 
-// #[cfg(test)]
-// mod mock;
+#[cfg(test)]
+mod mock;
 
-// #[cfg(test)]
-// mod tests;
+#[cfg(test)]
+mod tests;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migrations;
+
 pub type Id = u32;
 use frame_support::pallet_prelude::*;
 use frame_system::pallet_prelude::*;
@@ -22,8 +24,14 @@ use frame_support::pallet_prelude::BoundedVec;
 use scale_info::TypeInfo;
 use sp_runtime::traits::Hash;
 use sp_runtime::ArithmeticError;
-use frame_support::traits::Randomness;
+use frame_support::traits::{Randomness, Currency, ReservableCurrency};
+use sp_runtime::traits::Saturating;
 use frame_support::dispatch::Vec;
+use review_primitives::validation::{validate_text, TextError};
+use review_primitives::reputation::{RatingWeight, ReputationHandler, ReviewOutcome};
+use review_primitives::profiles::{OnReviewerRemoved, ReviewerProfiles};
+
+pub type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -34,24 +42,46 @@ pub mod pallet {
 	
 	pub struct Reviewer<T:Config> {
 		pub id: T::Hash,
-		pub name: Name,
-		pub star: Option<u32>, 
+		pub name: BoundedVec<u8, T::NameLimit>,
+		/// Reputation of the owner, computed from the outcome of their reviews.
+		pub reputation: u32,
 		pub owner: T::AccountId,
 
 	}
 
+	/// Review outcomes of an account, the reputation is derived from them.
+	#[derive(Encode, Decode, TypeInfo, Clone, Default, MaxEncodedLen, PartialEq, Eq, RuntimeDebug)]
+	pub struct ReputationStats {
+		pub helpful: u32,
+		pub unhelpful: u32,
+		/// Reviews that stood through their challenge period.
+		pub matured: u32,
+		/// Reviews removed by moderation.
+		pub removed: u32,
+	}
 
-	#[derive(Encode,Decode, TypeInfo, Clone,PartialEq, RuntimeDebug)]
-	pub struct Name(Vec<u8>);
-	impl MaxEncodedLen for Name {
-		fn max_encoded_len() -> usize {
-			20
+	impl ReputationStats {
+		const HELPFUL_POINTS: u32 = 2;
+		const UNHELPFUL_POINTS: u32 = 2;
+		const MATURED_POINTS: u32 = 10;
+		const REMOVED_POINTS: u32 = 50;
+
+		/// Points earned minus points lost, never below zero.
+		pub fn score(&self) -> u32 {
+			let earned = self.helpful.saturating_mul(Self::HELPFUL_POINTS)
+				.saturating_add(self.matured.saturating_mul(Self::MATURED_POINTS));
+			let lost = self.unhelpful.saturating_mul(Self::UNHELPFUL_POINTS)
+				.saturating_add(self.removed.saturating_mul(Self::REMOVED_POINTS));
+			earned.saturating_sub(lost)
 		}
 	}
 
+	/// Version 1 replaces the owner supplied `star` of reviewers with a computed reputation.
+	pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
-	#[pallet::without_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	
@@ -62,6 +92,29 @@ pub mod pallet {
 
 		#[pallet::constant]
 		type MaxAccount: Get<u32>;
+
+		#[pallet::constant]
+		type NameLimit: Get<u32>;
+
+		#[pallet::constant]
+		type NameMinLimit: Get<u32>;
+
+		/// Currency used to reserve registration deposits.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// Deposit reserved for every reviewer, on top of the per-byte deposit.
+		#[pallet::constant]
+		type ReviewerDepositBase: Get<BalanceOf<Self>>;
+
+		/// Deposit reserved per byte of reviewer name.
+		#[pallet::constant]
+		type ReviewerDepositPerByte: Get<BalanceOf<Self>>;
+
+		/// Origin allowed to remove abusive reviewers.
+		type ForceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Notified when a reviewer is removed, e.g. to detach the reviews written under it.
+		type OnReviewerRemoved: OnReviewerRemoved<Self::Hash>;
 	}
 
 	
@@ -77,12 +130,23 @@ pub mod pallet {
 	#[pallet::getter(fn reviewers_owned)]
 	pub type ReviewersAccountOwned<T:Config> = StorageMap<_, Blake2_128Concat, T::AccountId, BoundedVec<T::Hash, T::MaxAccount> , ValueQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn reviewer_deposit)]
+	pub type ReviewerDeposits<T:Config> = StorageMap<_, Blake2_128Concat, T::Hash, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn reputation_stats)]
+	pub type ReputationOf<T:Config> = StorageMap<_, Blake2_128Concat, T::AccountId, ReputationStats, ValueQuery>;
+
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		CreateReviewer {reviewer: T::Hash, who: T::AccountId},
-		UpdateReviewer {reviewer: T::Hash}
+		UpdateReviewer {reviewer: T::Hash},
+		RemoveReviewer {reviewer: T::Hash},
+		ForceRemoveReviewer {reviewer: T::Hash, slashed: BalanceOf<T>},
+		ReputationUpdated {who: T::AccountId, reputation: u32},
 	}
 
 	#[pallet::error]
@@ -91,6 +155,11 @@ pub mod pallet {
 		NotOwnedAccountReviewer,
 		DuplicateReviewer,
 		TooManyOwned,
+		InsufficientDeposit,
+		EmptyField,
+		InvalidUtf8,
+		NameTooShort,
+		NameTooLong,
 	}
 
 	#[pallet::hooks]
@@ -103,20 +172,26 @@ pub mod pallet {
 		
 		#[pallet::call_index(0)]
 		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
-		pub fn create_reviewer(origin: OriginFor<T>, name: Name, star: Option<u32>) -> DispatchResult {
+		pub fn create_reviewer(origin: OriginFor<T>, name: Vec<u8>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
+			let name = Self::bounded_name(name)?;
+
 			let id = Self::id_random();
 
 			let reviewer = Reviewer::<T> {
 				id: id.clone(),
 				name: name,
-				star: star,
+				reputation: Self::reputation(&who),
 				owner: who.clone()
 			};
 
 			ensure!(!Reviewers::<T>::contains_key(&reviewer.id), Error::<T>::DuplicateReviewer);
 
+			let deposit = Self::deposit_for(&reviewer.name);
+			T::Currency::reserve(&who, deposit).map_err(|_| Error::<T>::InsufficientDeposit)?;
+			ReviewerDeposits::<T>::insert(&id, deposit);
+
 			let reviewer_total = Self::count_reviewers();
             let new_reviewer_total = reviewer_total.checked_add(1).ok_or(ArithmeticError::Overflow)?;
 
@@ -135,17 +210,28 @@ pub mod pallet {
 
 		#[pallet::call_index(1)]
 		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
-		pub fn update_reviewer(origin: OriginFor<T>, id: T::Hash, name: Name, star: Option<u32>) -> DispatchResult {
+		pub fn update_reviewer(origin: OriginFor<T>, id: T::Hash, name: Vec<u8>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
+			let name = Self::bounded_name(name)?;
+
 			let reviewer = Reviewers::<T>::get(id.clone()).ok_or(Error::<T>::ReviewerNotFound)?;
 
 			ensure!(reviewer.owner.clone() == who, Error::<T>::NotOwnedAccountReviewer);
+
+			// deposit follows the size of the name
+			let old_deposit = Self::reviewer_deposit(&id);
+			let new_deposit = Self::deposit_for(&name);
+			if new_deposit > old_deposit {
+				T::Currency::reserve(&who, new_deposit - old_deposit).map_err(|_| Error::<T>::InsufficientDeposit)?;
+			} else {
+				T::Currency::unreserve(&who, old_deposit - new_deposit);
+			}
+			ReviewerDeposits::<T>::insert(&id, new_deposit);
 			
 			Reviewers::<T>::try_mutate(&id, |change| {
 				if let Some(reviewer) = change {
 					reviewer.name = name;
-					reviewer.star = star;
 					return Ok(());
 				}
 				Err(())
@@ -155,12 +241,83 @@ pub mod pallet {
 			
 			Ok(())
 		}
+
+		/// Remove a reviewer of the caller and refund its deposit. Reviews written under it are
+		/// detached from it, the weight of the reviews it did not have is refunded.
+		#[pallet::call_index(2)]
+		#[pallet::weight(Pallet::<T>::removal_weight(T::OnReviewerRemoved::max_weight()).ref_time())]
+		pub fn remove_reviewer(origin: OriginFor<T>, id: T::Hash) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let reviewer = Reviewers::<T>::get(id.clone()).ok_or(Error::<T>::ReviewerNotFound)?;
+
+			ensure!(reviewer.owner.clone() == who, Error::<T>::NotOwnedAccountReviewer);
+
+			let (deposit, detached) = Self::remove(&reviewer);
+			T::Currency::unreserve(&who, deposit);
+
+			Self::deposit_event(Event::RemoveReviewer { reviewer: id });
+
+			Ok(Some(Self::removal_weight(detached)).into())
+		}
+
+		/// Remove an abusive reviewer and slash the deposit of its owner.
+		#[pallet::call_index(3)]
+		#[pallet::weight(Pallet::<T>::removal_weight(T::OnReviewerRemoved::max_weight()).ref_time())]
+		pub fn force_remove_reviewer(origin: OriginFor<T>, id: T::Hash) -> DispatchResultWithPostInfo {
+			T::ForceOrigin::ensure_origin(origin)?;
+
+			let reviewer = Reviewers::<T>::get(id.clone()).ok_or(Error::<T>::ReviewerNotFound)?;
+
+			let (deposit, detached) = Self::remove(&reviewer);
+			let (_, unslashed) = T::Currency::slash_reserved(&reviewer.owner, deposit);
+
+			Self::deposit_event(Event::ForceRemoveReviewer { reviewer: id, slashed: deposit - unslashed });
+
+			Ok(Some(Self::removal_weight(detached)).into())
+		}
 	}
 
 	
 }
 
 impl<T: Config> Pallet<T> {
+	/// Reputation of `who`, shared by all the reviewers they own.
+	pub fn reputation(who: &T::AccountId) -> u32 {
+		Self::reputation_stats(who).score()
+	}
+
+	fn bounded_name(name: Vec<u8>) -> Result<BoundedVec<u8, T::NameLimit>, Error<T>> {
+		validate_text(name, T::NameMinLimit::get()).map_err(|error| match error {
+			TextError::Empty => Error::<T>::EmptyField,
+			TextError::TooShort => Error::<T>::NameTooShort,
+			TextError::TooLong => Error::<T>::NameTooLong,
+			TextError::InvalidUtf8 => Error::<T>::InvalidUtf8,
+		})
+	}
+
+	fn deposit_for(name: &[u8]) -> BalanceOf<T> {
+		let bytes = name.len() as u32;
+		T::ReviewerDepositBase::get().saturating_add(T::ReviewerDepositPerByte::get().saturating_mul(bytes.into()))
+	}
+
+	/// Drop a reviewer from storage, returning the deposit that was held for it and the weight
+	/// of detaching its reviews.
+	fn remove(reviewer: &Reviewer<T>) -> (BalanceOf<T>, Weight) {
+		Reviewers::<T>::remove(&reviewer.id);
+		ReviewersAccountOwned::<T>::mutate(&reviewer.owner, |list_reviewer| {
+			list_reviewer.retain(|id| *id != reviewer.id)
+		});
+		CountReviewers::<T>::mutate(|count| *count = count.saturating_sub(1));
+		let detached = T::OnReviewerRemoved::on_reviewer_removed(&reviewer.id);
+		(ReviewerDeposits::<T>::take(&reviewer.id), detached)
+	}
+
+	/// Weight of removing a reviewer whose reviews took `detached` to let go of.
+	fn removal_weight(detached: Weight) -> Weight {
+		Weight::from_ref_time(10_000).saturating_add(T::DbWeight::get().writes(4)).saturating_add(detached)
+	}
+
 	fn id_random() -> T::Hash {
 		let block_number = <frame_system::Pallet<T>>::block_number();
 		let (seed,_) = T::IdRandom::random_seed();
@@ -168,3 +325,53 @@ impl<T: Config> Pallet<T> {
 
 	}
 }
+
+impl<T: Config> ReputationHandler<T::AccountId> for Pallet<T> {
+	fn note_outcome(author: &T::AccountId, outcome: ReviewOutcome) {
+		let stats = ReputationOf::<T>::mutate(author, |stats| {
+			match outcome {
+				ReviewOutcome::Voted { helpful: true } => stats.helpful = stats.helpful.saturating_add(1),
+				ReviewOutcome::Voted { helpful: false } => stats.unhelpful = stats.unhelpful.saturating_add(1),
+				ReviewOutcome::VoteWithdrawn { helpful: true } => stats.helpful = stats.helpful.saturating_sub(1),
+				ReviewOutcome::VoteWithdrawn { helpful: false } => stats.unhelpful = stats.unhelpful.saturating_sub(1),
+				ReviewOutcome::VotesDropped { helpful, unhelpful } => {
+					stats.helpful = stats.helpful.saturating_sub(helpful);
+					stats.unhelpful = stats.unhelpful.saturating_sub(unhelpful);
+				},
+				ReviewOutcome::Matured => stats.matured = stats.matured.saturating_add(1),
+				ReviewOutcome::Removed => stats.removed = stats.removed.saturating_add(1),
+			}
+			stats.clone()
+		});
+
+		let reputation = stats.score();
+		for id in Self::reviewers_owned(author) {
+			Reviewers::<T>::mutate(&id, |reviewer| {
+				if let Some(reviewer) = reviewer {
+					reviewer.reputation = reputation;
+				}
+			});
+		}
+
+		Self::deposit_event(Event::ReputationUpdated { who: author.clone(), reputation });
+	}
+
+	/// The stats and profile list of the author, then every profile they own.
+	fn outcome_weight() -> Weight {
+		let profiles = T::MaxAccount::get() as u64;
+		T::DbWeight::get().reads_writes(2 + profiles, 1 + profiles)
+	}
+}
+
+impl<T: Config> ReviewerProfiles<T::AccountId, T::Hash> for Pallet<T> {
+	fn is_owner(who: &T::AccountId, reviewer: &T::Hash) -> bool {
+		ReviewersAccountOwned::<T>::get(who).contains(reviewer)
+	}
+}
+
+/// Authors weigh one plus their reputation, so authors without one still count.
+impl<T: Config> RatingWeight<T::AccountId> for Pallet<T> {
+	fn weight_of(author: &T::AccountId) -> u32 {
+		Self::reputation(author).saturating_add(1)
+	}
+}
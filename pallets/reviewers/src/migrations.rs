@@ -0,0 +1,77 @@
+use super::*;
+use core::marker::PhantomData;
+use frame_support::pallet_prelude::*;
+use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade};
+
+pub mod v1 {
+    use super::*;
+
+    /// Reviewer as stored before version 1.
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldReviewer<Hash, AccountId, Name> {
+        pub id: Hash,
+        pub name: Name,
+        pub star: Option<u32>,
+        pub owner: AccountId,
+    }
+
+    /// Drop the owner supplied `star` of reviewers in favour of the computed reputation.
+    ///
+    /// Names used to be unbounded, longer ones are cut to `NameLimit`.
+    pub struct MigrateToV1<T>(PhantomData<T>);
+
+    /// `name` cut to `NameLimit` bytes, without splitting a character of a UTF-8 name.
+    fn truncated_name<T: Config>(mut name: Vec<u8>) -> BoundedVec<u8, T::NameLimit> {
+        let limit = T::NameLimit::get() as usize;
+        if name.len() > limit {
+            let mut end = limit;
+            if let Ok(text) = core::str::from_utf8(&name) {
+                while !text.is_char_boundary(end) {
+                    end -= 1;
+                }
+            }
+            name.truncate(end);
+        }
+        BoundedVec::truncate_from(name)
+    }
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let onchain_version = Pallet::<T>::on_chain_storage_version();
+            if onchain_version >= 1 {
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut count = 0u64;
+            Reviewers::<T>::translate::<OldReviewer<T::Hash, T::AccountId, Vec<u8>>, _>(
+                |_, old| {
+                    count += 1;
+                    Some(Reviewer::<T> {
+                        id: old.id,
+                        name: truncated_name::<T>(old.name),
+                        reputation: Pallet::<T>::reputation(&old.owner),
+                        owner: old.owner,
+                    })
+                },
+            );
+            StorageVersion::new(1).put::<Pallet<T>>();
+
+            T::DbWeight::get().reads_writes(count * 2 + 1, count + 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            Ok((Reviewers::<T>::iter_keys().count() as u32).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            let reviewer_count = u32::decode(&mut &state[..]).map_err(|_| "failed to decode reviewer count")?;
+
+            ensure!(Pallet::<T>::on_chain_storage_version() == 1, "storage version not updated");
+            ensure!(Reviewers::<T>::iter().count() as u32 == reviewer_count, "reviewers were lost");
+
+            Ok(())
+        }
+    }
+}
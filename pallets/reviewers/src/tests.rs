@@ -0,0 +1,195 @@
+use crate::{mock::*, migrations::v1::{MigrateToV1, OldReviewer}, Error};
+use frame_support::{
+	assert_noop, assert_ok,
+	pallet_prelude::{BoundedVec, ConstU32, StorageVersion},
+	storage::unhashed,
+	traits::{GetStorageVersion, OnRuntimeUpgrade, ReservableCurrency},
+};
+use review_primitives::{
+	profiles::ReviewerProfiles,
+	reputation::{ReputationHandler, ReviewOutcome},
+};
+use sp_core::H256;
+
+#[test]
+fn create_reviewer_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Reviewers::create_reviewer(RuntimeOrigin::signed(1), b"alice".to_vec()));
+		assert_eq!(Reviewers::count_reviewers(), 1);
+		assert_eq!(Reviewers::reviewers_owned(1).len(), 1);
+		// base deposit plus one per byte of name
+		assert_eq!(Balances::reserved_balance(1), 10 + 5);
+	});
+}
+
+#[test]
+fn create_reviewer_rejects_empty_name() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Reviewers::create_reviewer(RuntimeOrigin::signed(1), Vec::new()),
+			Error::<Test>::EmptyField
+		);
+	});
+}
+
+#[test]
+fn create_reviewer_rejects_short_name() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Reviewers::create_reviewer(RuntimeOrigin::signed(1), b"a".to_vec()),
+			Error::<Test>::NameTooShort
+		);
+	});
+}
+
+#[test]
+fn create_reviewer_rejects_long_name() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Reviewers::create_reviewer(RuntimeOrigin::signed(1), vec![b'a'; 9]),
+			Error::<Test>::NameTooLong
+		);
+	});
+}
+
+#[test]
+fn create_reviewer_rejects_invalid_utf8() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Reviewers::create_reviewer(RuntimeOrigin::signed(1), vec![0xc0, 0xaf]),
+			Error::<Test>::InvalidUtf8
+		);
+	});
+}
+
+#[test]
+fn update_reviewer_validates_name() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Reviewers::create_reviewer(RuntimeOrigin::signed(1), b"alice".to_vec()));
+		let id = Reviewers::reviewers_owned(1)[0];
+
+		assert_noop!(
+			Reviewers::update_reviewer(RuntimeOrigin::signed(1), id, vec![b'a'; 9]),
+			Error::<Test>::NameTooLong
+		);
+		assert_ok!(Reviewers::update_reviewer(RuntimeOrigin::signed(1), id, b"al".to_vec()));
+		assert_eq!(Balances::reserved_balance(1), 10 + 2);
+	});
+}
+
+#[test]
+fn reputation_follows_review_outcomes() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Reviewers::create_reviewer(RuntimeOrigin::signed(1), b"alice".to_vec()));
+		let id = Reviewers::reviewers_owned(1)[0];
+
+		Reviewers::note_outcome(&1, ReviewOutcome::Matured);
+		Reviewers::note_outcome(&1, ReviewOutcome::Voted { helpful: true });
+		Reviewers::note_outcome(&1, ReviewOutcome::Voted { helpful: false });
+		Reviewers::note_outcome(&1, ReviewOutcome::VoteWithdrawn { helpful: false });
+		assert_eq!(Reviewers::reviewers(id).unwrap().reputation, 10 + 2);
+
+		// votes of a review that is gone no longer count
+		Reviewers::note_outcome(&1, ReviewOutcome::Voted { helpful: true });
+		Reviewers::note_outcome(&1, ReviewOutcome::Voted { helpful: false });
+		Reviewers::note_outcome(&1, ReviewOutcome::VotesDropped { helpful: 2, unhelpful: 1 });
+		assert_eq!(Reviewers::reputation_stats(1).helpful, 0);
+		assert_eq!(Reviewers::reviewers(id).unwrap().reputation, 10);
+
+		// removals outweigh what was earned, the score does not go below zero
+		Reviewers::note_outcome(&1, ReviewOutcome::Removed);
+		assert_eq!(Reviewers::reviewers(id).unwrap().reputation, 0);
+
+		// new reviewers start from the reputation of their owner
+		Reviewers::note_outcome(&1, ReviewOutcome::Matured);
+		Reviewers::note_outcome(&1, ReviewOutcome::Matured);
+		Reviewers::note_outcome(&1, ReviewOutcome::Matured);
+		Reviewers::note_outcome(&1, ReviewOutcome::Matured);
+		System::set_block_number(2);
+		assert_ok!(Reviewers::create_reviewer(RuntimeOrigin::signed(1), b"bob".to_vec()));
+		let other = Reviewers::reviewers_owned(1)[1];
+		assert_eq!(Reviewers::reviewers(other).unwrap().reputation, 2);
+	});
+}
+
+#[test]
+fn migration_drops_reviewer_star() {
+	new_test_ext().execute_with(|| {
+		let id = H256::repeat_byte(1);
+		let old = OldReviewer { id, name: BoundedVec::<u8, ConstU32<8>>::truncate_from(b"alice".to_vec()), star: Some(5), owner: 1u64 };
+		unhashed::put(&crate::Reviewers::<Test>::hashed_key_for(id), &old);
+		StorageVersion::new(0).put::<Reviewers>();
+
+		MigrateToV1::<Test>::on_runtime_upgrade();
+
+		let reviewer = Reviewers::reviewers(id).unwrap();
+		assert_eq!(reviewer.name.into_inner(), b"alice".to_vec());
+		assert_eq!(reviewer.reputation, 0);
+		assert_eq!(reviewer.owner, 1);
+		assert_eq!(Reviewers::on_chain_storage_version(), 1);
+	});
+}
+
+#[test]
+fn migration_cuts_long_reviewer_names() {
+	new_test_ext().execute_with(|| {
+		let id = H256::repeat_byte(1);
+		let utf8_id = H256::repeat_byte(2);
+		// names had no limit, the mock allows eight bytes
+		let old = OldReviewer { id, name: b"alexandria".to_vec(), star: None, owner: 1u64 };
+		unhashed::put(&crate::Reviewers::<Test>::hashed_key_for(id), &old);
+		let old = OldReviewer { id: utf8_id, name: "aëëëë".as_bytes().to_vec(), star: None, owner: 2u64 };
+		unhashed::put(&crate::Reviewers::<Test>::hashed_key_for(utf8_id), &old);
+		StorageVersion::new(0).put::<Reviewers>();
+
+		MigrateToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Reviewers::reviewers(id).unwrap().name.into_inner(), b"alexandr".to_vec());
+		// the name is cut before a character that does not fit whole
+		assert_eq!(Reviewers::reviewers(utf8_id).unwrap().name.into_inner(), "aëëë".as_bytes().to_vec());
+	});
+}
+
+#[test]
+fn only_owners_write_under_a_profile() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Reviewers::create_reviewer(RuntimeOrigin::signed(1), b"alice".to_vec()));
+		let id = Reviewers::reviewers_owned(1)[0];
+
+		assert!(Reviewers::is_owner(&1, &id));
+		assert!(!Reviewers::is_owner(&2, &id));
+	});
+}
+
+#[test]
+fn remove_reviewer_refunds_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Reviewers::create_reviewer(RuntimeOrigin::signed(1), b"alice".to_vec()));
+		let id = Reviewers::reviewers_owned(1)[0];
+
+		assert_noop!(Reviewers::remove_reviewer(RuntimeOrigin::signed(2), id), Error::<Test>::NotOwnedAccountReviewer);
+		assert_ok!(Reviewers::remove_reviewer(RuntimeOrigin::signed(1), id));
+		assert!(Reviewers::reviewers(id).is_none());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 1_000);
+	});
+}
+
+#[test]
+fn force_remove_reviewer_slashes_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Reviewers::create_reviewer(RuntimeOrigin::signed(1), b"alice".to_vec()));
+		let id = Reviewers::reviewers_owned(1)[0];
+
+		assert_noop!(
+			Reviewers::force_remove_reviewer(RuntimeOrigin::signed(1), id),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(Reviewers::force_remove_reviewer(RuntimeOrigin::root(), id));
+		System::assert_last_event(crate::Event::<Test>::ForceRemoveReviewer { reviewer: id, slashed: 10 + 5 }.into());
+		assert!(Reviewers::reviewers(id).is_none());
+		assert!(Reviewers::reviewers_owned(1).is_empty());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 1_000 - 10 - 5);
+	});
+}
@@ -0,0 +1,5 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod profiles;
+pub mod reputation;
+pub mod validation;
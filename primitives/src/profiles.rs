@@ -0,0 +1,34 @@
+use frame_support::weights::Weight;
+
+/// Reviewer profiles that reviews can be written under.
+pub trait ReviewerProfiles<AccountId, Hash> {
+    /// Whether `who` owns the reviewer profile `reviewer`.
+    fn is_owner(who: &AccountId, reviewer: &Hash) -> bool;
+}
+
+/// No profiles exist, so none can be used.
+impl<AccountId, Hash> ReviewerProfiles<AccountId, Hash> for () {
+    fn is_owner(_who: &AccountId, _reviewer: &Hash) -> bool {
+        false
+    }
+}
+
+/// Hook called once a reviewer profile has been removed, so reviews written under it stop
+/// pointing at it.
+pub trait OnReviewerRemoved<Hash> {
+    /// Returns the weight it used.
+    fn on_reviewer_removed(reviewer: &Hash) -> Weight;
+
+    /// Weight of `on_reviewer_removed` at its worst.
+    fn max_weight() -> Weight;
+}
+
+impl<Hash> OnReviewerRemoved<Hash> for () {
+    fn on_reviewer_removed(_reviewer: &Hash) -> Weight {
+        Weight::zero()
+    }
+
+    fn max_weight() -> Weight {
+        Weight::zero()
+    }
+}
@@ -0,0 +1,45 @@
+use frame_support::{weights::Weight, RuntimeDebug};
+
+/// Something that happened to a review and reflects on its author.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum ReviewOutcome {
+    /// Someone voted the review helpful, or unhelpful when `helpful` is `false`.
+    Voted { helpful: bool },
+    /// A vote on the review was taken back.
+    VoteWithdrawn { helpful: bool },
+    /// The review is gone, and so are the votes it received.
+    VotesDropped { helpful: u32, unhelpful: u32 },
+    /// The review stood through its challenge period.
+    Matured,
+    /// Moderation removed the review.
+    Removed,
+}
+
+/// Keeps the reputation of review authors. Implemented by the reviewers pallet and fed by the
+/// review pallet.
+pub trait ReputationHandler<AccountId> {
+    fn note_outcome(author: &AccountId, outcome: ReviewOutcome);
+
+    /// Weight of a single `note_outcome` at its worst.
+    fn outcome_weight() -> Weight;
+}
+
+impl<AccountId> ReputationHandler<AccountId> for () {
+    fn note_outcome(_author: &AccountId, _outcome: ReviewOutcome) {}
+
+    fn outcome_weight() -> Weight {
+        Weight::zero()
+    }
+}
+
+/// Weight of the star of a review in the weighted rating of an app.
+pub trait RatingWeight<AccountId> {
+    fn weight_of(author: &AccountId) -> u32;
+}
+
+/// Every review counts the same.
+impl<AccountId> RatingWeight<AccountId> for () {
+    fn weight_of(_author: &AccountId) -> u32 {
+        1
+    }
+}
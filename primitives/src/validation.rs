@@ -0,0 +1,33 @@
+use frame_support::inherent::Vec;
+use frame_support::pallet_prelude::{BoundedVec, Get};
+use frame_support::RuntimeDebug;
+
+/// Why a piece of user supplied text was rejected. Pallets map it to their own errors.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum TextError {
+    /// Input is empty but the field is required.
+    Empty,
+    /// Input is shorter than the minimum length.
+    TooShort,
+    /// Input is longer than the bound `S`.
+    TooLong,
+    /// Input is not valid UTF-8.
+    InvalidUtf8,
+}
+
+/// Bound `input` to `S` bytes after checking it holds at least `min_len` bytes of valid UTF-8.
+/// A `min_len` of `0` makes the field optional.
+pub fn validate_text<S: Get<u32>>(input: Vec<u8>, min_len: u32) -> Result<BoundedVec<u8, S>, TextError> {
+    if input.is_empty() && min_len > 0 {
+        return Err(TextError::Empty);
+    }
+    if (input.len() as u32) < min_len {
+        return Err(TextError::TooShort);
+    }
+
+    // check the length first so oversized input is never scanned
+    let bounded: BoundedVec<u8, S> = input.try_into().map_err(|_| TextError::TooLong)?;
+    core::str::from_utf8(&bounded).map_err(|_| TextError::InvalidUtf8)?;
+
+    Ok(bounded)
+}
@@ -40,6 +40,7 @@ pub use frame_support::{
 	StorageValue,
 };
 pub use frame_system::Call as SystemCall;
+use frame_system::EnsureRoot;
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
 use pallet_transaction_payment::{ConstFeeMultiplier, CurrencyAdapter, Multiplier};
@@ -117,10 +118,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 104,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -257,6 +258,9 @@ impl pallet_timestamp::Config for Runtime {
 /// Existential deposit.
 pub const EXISTENTIAL_DEPOSIT: u128 = 500;
 
+pub const UNIT: Balance = 1_000_000_000_000;
+pub const MILLIUNIT: Balance = UNIT / 1_000;
+
 impl pallet_balances::Config for Runtime {
 	type MaxLocks = ConstU32<50>;
 	type MaxReserves = ();
@@ -305,6 +309,23 @@ parameter_types! {
 parameter_types! {
 	pub const ContentLimit : u32 = 1000000000u32;
 }
+
+parameter_types! {
+	pub const AppDepositBase: Balance = 10 * UNIT;
+	pub const ReviewerDepositBase: Balance = UNIT;
+	pub const ReviewDepositBase: Balance = UNIT;
+	pub const DepositPerByte: Balance = MILLIUNIT;
+}
+
+parameter_types! {
+	pub const CommentRateWindow: BlockNumber = HOURS;
+}
+
+parameter_types! {
+	pub const ReviewBond: Balance = 5 * UNIT;
+	pub const ChallengePeriod: BlockNumber = 7 * DAYS;
+	pub const FlaggerReward: Perbill = Perbill::from_percent(50);
+}
 impl pallet_template::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 }
@@ -314,12 +335,20 @@ impl pallet_reviewers::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type IdRandom = RandomnessCollectiveFlip;
 	type MaxAccount = MaxAccount;
+	type NameLimit = ConstU32<20_u32>;
+	type NameMinLimit = ConstU32<1_u32>;
+	type Currency = Balances;
+	type ReviewerDepositBase = ReviewerDepositBase;
+	type ReviewerDepositPerByte = DepositPerByte;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type OnReviewerRemoved = ReviewModule;
 }
 
 /// Configure the pallet-review in pallets/review.
 impl pallet_review::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type ContentLimit = ContentLimit;
+	type ContentMinLimit = ConstU32<1_u32>;
 	type Moment = Moment;
 	// type Star = Star;
 	// type StarLimit = StarLimit;
@@ -327,6 +356,33 @@ impl pallet_review::Config for Runtime {
 	type ReviewTime = Timestamp;
 	type ReviewOwnerLimit = ReviewOwnerLimit;
 	type ReviewOwnerByAppLimit = ReviewOwnerByAppLimit;
+	type RatingHandler = PalletApps;
+	type ReviewDepositBase = ReviewDepositBase;
+	type ReviewDepositPerByte = DepositPerByte;
+	type ModeratorOrigin = EnsureRoot<AccountId>;
+	// every reaped review may carry `MaxCommentsPerReview` comments and `MaxVotesPerReview`
+	// votes, keep a full batch well within a block
+	type ReapBatchSize = ConstU32<5_u32>;
+	type MaxRevisions = ConstU32<16_u32>;
+	type MaxVotesPerReview = ConstU32<500_u32>;
+	type ReplyLimit = ConstU32<512_u32>;
+	type CommentLimit = ConstU32<512_u32>;
+	type MaxCommentDepth = ConstU32<4_u32>;
+	type MaxCommentsPerReview = ConstU32<1000_u32>;
+	type CommentRateWindow = CommentRateWindow;
+	type MaxCommentsPerWindow = ConstU32<30_u32>;
+	type FlagThreshold = ConstU32<5_u32>;
+	type ReviewBond = ReviewBond;
+	type ChallengePeriod = ChallengePeriod;
+	// bonds past this many wait for the next blocks
+	type MaxBondUnlocksPerBlock = ConstU32<50_u32>;
+	type FlaggerReward = FlaggerReward;
+	// slashed bonds are burnt
+	type Slash = ();
+	type Reputation = Reviewers;
+	type RatingWeight = Reviewers;
+	type Reviewers = Reviewers;
+	type MaxReviewsPerReviewer = ConstU32<100_u32>;
 }
 
 impl pallet_apps::Config for Runtime {
@@ -334,11 +390,23 @@ impl pallet_apps::Config for Runtime {
 	type AppId = AppId;
 	type Moment = Moment;
 	type AppTime = Timestamp;
+	type Currency = Balances;
+	type AppDepositBase = AppDepositBase;
+	type DepositPerByte = DepositPerByte;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type OnAppDestroyed = ReviewModule;
 	type Star = Star;
 	type AppNameLimit = ConstU32<255_u32>;
+	type AppNameMinLimit = ConstU32<1_u32>;
 	type AppSymbolLimit = ConstU32<125_u32>;
+	type AppSymbolMinLimit = ConstU32<1_u32>;
 	type StarLimit = ConstU32<5_u32>;
 	type AppOwnerLimit = ConstU32<12_u32>;
+	type MaxMaintainers = ConstU32<16_u32>;
+	type MaxReleases = ConstU32<32_u32>;
+	type CategoryNameLimit = ConstU32<64_u32>;
+	type TagLimit = ConstU32<32_u32>;
+	type MaxTags = ConstU32<8_u32>;
 }
 
 
@@ -390,6 +458,12 @@ pub type UncheckedExtrinsic =
 	generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
+/// Storage migrations applied on runtime upgrade.
+pub type Migrations = (
+	pallet_apps::migrations::v1::MigrateToV1<Runtime>,
+	pallet_review::migrations::v1::MigrateToV1<Runtime>,
+	pallet_reviewers::migrations::v1::MigrateToV1<Runtime>,
+);
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -397,6 +471,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -414,6 +489,44 @@ mod benches {
 	);
 }
 
+/// DTOs returned by the review platform runtime API.
+pub type AppInfo = pallet_review_rpc_runtime_api::AppInfo<AccountId, Hash, AppId, Moment, Star>;
+pub type ReviewInfo = pallet_review_rpc_runtime_api::ReviewInfo<AccountId, Hash, Star>;
+pub type ReviewerInfo = pallet_review_rpc_runtime_api::ReviewerInfo<AccountId, Hash>;
+
+fn app_info(app_hash: Hash, app: pallet_apps::App<Runtime>) -> AppInfo {
+	AppInfo {
+		id: app.id(),
+		hash: app_hash,
+		name: app.name().into_inner(),
+		symbol: app.symbol().into_inner(),
+		owner: app.owner(),
+		created_at: app.created_at(),
+		star: PalletApps::star_of(&app_hash),
+		archived: PalletApps::is_archived(&app_hash),
+	}
+}
+
+fn review_info(review_id: Hash, review: pallet_review::Review<Runtime>) -> ReviewInfo {
+	ReviewInfo {
+		id: review_id,
+		app: review.app_id,
+		star: review.star,
+		title: review.title.into_inner(),
+		cons: review.cons.into_inner(),
+		pros: review.pros.into_inner(),
+		owner: review.owner,
+		created_date: review.created_date,
+		release: review.release.map(|version| (version.major, version.minor, version.patch)),
+		reviewer: review.reviewer,
+		hidden: ReviewModule::review_status(review_id) == pallet_review::ReviewStatus::Hidden,
+	}
+}
+
+fn page_limit(limit: u32) -> u32 {
+	limit.min(pallet_review_rpc_runtime_api::MAX_PAGE_SIZE)
+}
+
 impl_runtime_apis! {
 	impl sp_api::Core<Block> for Runtime {
 		fn version() -> RuntimeVersion {
@@ -562,6 +675,116 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_review_rpc_runtime_api::ReviewPlatformApi<Block, AccountId, Hash, AppId, Moment, Star> for Runtime {
+		fn app_by_id(app_id: AppId) -> Option<AppInfo> {
+			let app_hash = PalletApps::app_hash_of(app_id)?;
+			PalletApps::apps(app_hash).map(|app| app_info(app_hash, app))
+		}
+
+		fn apps_by_owner(owner: AccountId) -> Vec<AppInfo> {
+			PalletApps::apps_of(&owner).into_iter().map(|(app_hash, app)| app_info(app_hash, app)).collect()
+		}
+
+		fn apps_page(cursor: AppId, limit: u32) -> pallet_review_rpc_runtime_api::Page<AppInfo, AppId> {
+			let (apps, next) = PalletApps::apps_page(cursor, page_limit(limit));
+			pallet_review_rpc_runtime_api::Page {
+				items: apps.into_iter().map(|(app_hash, app)| app_info(app_hash, app)).collect(),
+				next,
+			}
+		}
+
+		fn reviews_for_app(app: Hash, cursor: u32, limit: u32) -> pallet_review_rpc_runtime_api::Page<ReviewInfo, u32> {
+			let (reviews, next) = ReviewModule::reviews_page(&app, cursor, page_limit(limit));
+			pallet_review_rpc_runtime_api::Page {
+				items: reviews.into_iter().map(|(review_id, review)| review_info(review_id, review)).collect(),
+				next,
+			}
+		}
+
+		fn reviews_by_account(account: AccountId) -> Vec<ReviewInfo> {
+			ReviewModule::reviews_of(&account)
+				.into_iter()
+				.map(|(review_id, review)| review_info(review_id, review))
+				.collect()
+		}
+
+		fn reviewer(id: Hash) -> Option<ReviewerInfo> {
+			Reviewers::reviewers(id).map(|reviewer| ReviewerInfo {
+				id: reviewer.id,
+				name: reviewer.name.into_inner(),
+				reputation: reviewer.reputation,
+				owner: reviewer.owner,
+			})
+		}
+
+		fn rating_summary(app: Hash) -> Option<pallet_review_rpc_runtime_api::RatingSummary> {
+			PalletApps::apps(app)?;
+			let rating = ReviewModule::app_ratings(app);
+			let averages = ReviewModule::rating_averages(app);
+			Some(pallet_review_rpc_runtime_api::RatingSummary {
+				count: rating.count,
+				average: rating.average(),
+				histogram: rating.histogram.into_inner(),
+				raw: averages.raw,
+				weighted: averages.weighted,
+			})
+		}
+
+		fn platform_events() -> Vec<pallet_review_rpc_runtime_api::PlatformEvent<AccountId, Hash, Star>> {
+			use pallet_review_rpc_runtime_api::PlatformEvent;
+
+			System::events()
+				.into_iter()
+				.filter_map(|record| match record.event {
+					RuntimeEvent::ReviewModule(pallet_review::Event::ReviewCreated { who, review_id, app_hash, star }) =>
+						Some(PlatformEvent::ReviewCreated { review: review_id, app: app_hash, author: who, star }),
+					RuntimeEvent::PalletApps(pallet_apps::Event::AppCreated { app_hash, .. }) =>
+						Some(PlatformEvent::AppCreated { app: app_hash }),
+					RuntimeEvent::PalletApps(pallet_apps::Event::AppUpdated { app_hash }) =>
+						Some(PlatformEvent::AppUpdated { app: app_hash }),
+					RuntimeEvent::PalletApps(pallet_apps::Event::AppStarUpdated { app_hash, star }) =>
+						Some(PlatformEvent::AppStarUpdated { app: app_hash, star }),
+					// a pinned star is what the app shows, clearing it falls back to the rating
+					RuntimeEvent::PalletApps(pallet_apps::Event::StarOverrideSet { app_hash, star }) =>
+						Some(PlatformEvent::AppStarUpdated { app: app_hash, star: Some(star) }),
+					RuntimeEvent::PalletApps(pallet_apps::Event::StarOverrideCleared { app_hash }) =>
+						Some(PlatformEvent::AppStarUpdated { app: app_hash, star: PalletApps::star_of(&app_hash) }),
+					_ => None,
+				})
+				.collect()
+		}
+
+		fn reviews_by_helpfulness(
+			app: Hash,
+			cursor: u32,
+			limit: u32,
+		) -> pallet_review_rpc_runtime_api::Page<pallet_review_rpc_runtime_api::RankedReview<AccountId, Hash, Star>, u32> {
+			let (reviews, next) = ReviewModule::helpful_reviews_page(&app, cursor, page_limit(limit));
+			pallet_review_rpc_runtime_api::Page {
+				items: reviews
+					.into_iter()
+					.map(|(review_id, review, tally)| pallet_review_rpc_runtime_api::RankedReview {
+						review: review_info(review_id, review),
+						helpful: tally.helpful,
+						unhelpful: tally.unhelpful,
+					})
+					.collect(),
+				next,
+			}
+		}
+
+		fn reputation(account: AccountId) -> u32 {
+			Reviewers::reputation(&account)
+		}
+
+		fn reviews_by_reviewer(reviewer: Hash) -> Vec<ReviewInfo> {
+			ReviewModule::reviews_of_reviewer(&reviewer)
+				.into_iter()
+				.map(|(review_id, review)| review_info(review_id, review))
+				.collect()
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (